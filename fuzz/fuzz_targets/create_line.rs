@@ -0,0 +1,25 @@
+#![no_main]
+
+use layered_nlp::{create_line_from_input_tokens, InputToken, LToken};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|texts: Vec<String>| {
+    let input = texts
+        .iter()
+        .map(|text| InputToken::text(text.clone(), Vec::new()))
+        .collect();
+
+    let ll_line = create_line_from_input_tokens(input, |text| text.encode_utf16().count());
+
+    // no text is lost nor added in tokenizing
+    let line_text: String = ll_line
+        .ll_tokens()
+        .iter()
+        .filter_map(|ll_token| match ll_token.token() {
+            LToken::Text(text, _) => Some(text.as_str()),
+            LToken::Value(_) => None,
+        })
+        .collect();
+    assert_eq!(line_text, texts.concat());
+    let _ = layered_nlp::debug_line(&ll_line).to_string();
+});
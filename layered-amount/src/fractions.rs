@@ -0,0 +1,189 @@
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// A fractional value like `3/4`, `one half` or `two and a half`.
+///
+/// Mixed numbers are kept as improper fractions, `two and a half` is `5/2`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fraction {
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+impl Fraction {
+    pub fn value(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+const NUMBER_WORDS: &[(&str, u64)] = &[
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("ten", 10),
+];
+
+/// "second" is left out, `one second` is rarely a fraction
+const DENOMINATOR_WORDS: &[(&str, u64)] = &[
+    ("half", 2),
+    ("halves", 2),
+    ("third", 3),
+    ("thirds", 3),
+    ("quarter", 4),
+    ("quarters", 4),
+    ("fourth", 4),
+    ("fourths", 4),
+    ("fifth", 5),
+    ("fifths", 5),
+    ("sixth", 6),
+    ("sixths", 6),
+    ("seventh", 7),
+    ("sevenths", 7),
+    ("eighth", 8),
+    ("eighths", 8),
+    ("ninth", 9),
+    ("ninths", 9),
+    ("tenth", 10),
+    ("tenths", 10),
+];
+
+fn lookup_word(words: &[(&str, u64)], text: &str) -> Option<u64> {
+    words
+        .iter()
+        .find(|(word, _)| word.eq_ignore_ascii_case(text))
+        .map(|(_, value)| *value)
+}
+
+/// Recognizes slash fractions (`3/4`), spelled fractions (`one half`,
+/// `a quarter`, `2 thirds`) and mixed numbers (`two and a half`, `1 and 3/4`).
+#[derive(Default)]
+pub struct FractionResolver(());
+
+impl FractionResolver {
+    /// Fraction whose numerator is `count`, matched right after `count_sel`.
+    fn match_fraction(
+        count_sel: &LLSelection,
+        count: u64,
+        is_digits: bool,
+    ) -> Option<(LLSelection, Fraction)> {
+        if is_digits {
+            if let Some((slash_sel, _)) = count_sel.match_first_forwards(&x::token_has_any(&['/']))
+            {
+                let (denominator_sel, (_, text)) = slash_sel
+                    .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))?;
+                let denominator = text.parse::<u64>().ok().filter(|den| *den != 0)?;
+
+                return Some((
+                    denominator_sel,
+                    Fraction {
+                        numerator: count,
+                        denominator,
+                    },
+                ));
+            }
+        }
+
+        let (space_sel, _) = count_sel.match_first_forwards(&x::whitespace())?;
+        let (denominator_sel, (_, text)) = space_sel
+            .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))?;
+        let denominator = lookup_word(DENOMINATOR_WORDS, text)?;
+
+        Some((
+            denominator_sel,
+            Fraction {
+                numerator: count,
+                denominator,
+            },
+        ))
+    }
+
+    /// The fractional part of a mixed number, like ` and a half`, matched right after `whole_sel`.
+    fn match_mixed_fraction(
+        whole_sel: &LLSelection,
+        whole: u64,
+    ) -> Option<(LLSelection, Fraction)> {
+        let (space_sel, _) = whole_sel.match_first_forwards(&x::whitespace())?;
+        let (and_sel, (_, text)) = space_sel
+            .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))?;
+        if !text.eq_ignore_ascii_case("and") {
+            return None;
+        }
+        let (space_sel, _) = and_sel.match_first_forwards(&x::whitespace())?;
+        let (count_sel, (tag, text)) =
+            space_sel.match_first_forwards(&x::all((x::attr::<TextTag>(), x::token_text())))?;
+        let (count, is_digits) = Self::count(tag, text, true)?;
+
+        let (fraction_sel, fraction) = Self::match_fraction(&count_sel, count, is_digits)?;
+        if fraction.numerator >= fraction.denominator {
+            // "two and five halves"
+            return None;
+        }
+
+        // too big for a `u64` numerator
+        let numerator = whole
+            .checked_mul(fraction.denominator)?
+            .checked_add(fraction.numerator)?;
+
+        Some((
+            fraction_sel,
+            Fraction {
+                numerator,
+                denominator: fraction.denominator,
+            },
+        ))
+    }
+
+    /// Value of a number token, and whether it is written with digits
+    fn count(tag: &TextTag, text: &str, allow_article: bool) -> Option<(u64, bool)> {
+        match tag {
+            TextTag::NATN => text.parse().ok().map(|count| (count, true)),
+            TextTag::WORD
+                if allow_article && ["a", "an"].contains(&text.to_lowercase().as_str()) =>
+            {
+                Some((1, false))
+            }
+            TextTag::WORD => lookup_word(NUMBER_WORDS, text).map(|count| (count, false)),
+            _ => None,
+        }
+    }
+}
+
+impl Resolver for FractionResolver {
+    type Attr = Fraction;
+
+    fn go(&self, mut search_range_sel: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let mut attrs = vec![];
+
+        while let Some((start_sel, (tag, text))) =
+            search_range_sel.find_first_by(&x::all((x::attr::<TextTag>(), x::token_text())))
+        {
+            let found = Self::count(tag, text, false)
+                .and_then(|(whole, _)| Self::match_mixed_fraction(&start_sel, whole))
+                .or_else(|| {
+                    let (count, is_digits) = Self::count(tag, text, true)?;
+                    Self::match_fraction(&start_sel, count, is_digits)
+                });
+
+            let matched_sel = match found {
+                Some((fraction_sel, fraction)) => {
+                    attrs.push(fraction_sel.finish_with_attr(fraction));
+                    fraction_sel
+                }
+                None => start_sel,
+            };
+
+            if let [_, Some(right_sel)] = search_range_sel.split_with(&matched_sel) {
+                search_range_sel = right_sel;
+            } else {
+                break;
+            }
+        }
+
+        attrs
+    }
+}
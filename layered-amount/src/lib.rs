@@ -4,11 +4,25 @@
 )]
 
 mod amounts;
+mod fractions;
+mod scaled_numbers;
+mod scientific_notation;
+mod units;
 
 pub use amounts::{Amount, AmountResolver};
+pub use fractions::{Fraction, FractionResolver};
+pub use scaled_numbers::{Number, ScaledNumberResolver};
 pub use rust_decimal;
+pub use scientific_notation::ScientificNotationResolver;
+pub use units::{
+    ConversionCandidate, Measurement, MeasurementResolver, UnitDimensions, UnitMismatchResolver,
+};
 
 #[cfg(test)]
 mod tests {
     mod amounts;
+    mod fractions;
+    mod scaled_numbers;
+    mod scientific_notation;
+    mod units;
 }
@@ -0,0 +1,134 @@
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::Amount;
+
+/// A numeric value spelled with words, like `2 million` or `a dozen`, or in
+/// scientific notation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Number {
+    pub value: f64,
+}
+
+/// Multiplies a number by the scale words following it: `3.5 billion`,
+/// `a dozen`, or chained as in `two hundred thousand`.
+///
+/// The number is either an [Amount] (run [crate::AmountResolver] first) or a
+/// number word. Numbers without a scale word are left alone.
+pub struct ScaledNumberResolver {
+    /// Lowercased words and their value, like `two` = 2
+    number_words: Vec<(String, f64)>,
+    /// Lowercased words and their multiplier, like `dozen` = 12
+    scales: Vec<(String, f64)>,
+}
+
+impl ScaledNumberResolver {
+    pub fn new(number_words: Vec<(&str, f64)>, scales: Vec<(&str, f64)>) -> Self {
+        let lowercase = |words: Vec<(&str, f64)>| -> Vec<(String, f64)> {
+            words
+                .into_iter()
+                .map(|(word, value)| (word.to_lowercase(), value))
+                .collect()
+        };
+
+        Self {
+            number_words: lowercase(number_words),
+            scales: lowercase(scales),
+        }
+    }
+
+    /// Numbers from "one" to "twelve", the tens, and "a" for `a dozen`.
+    /// Scales from "dozen" to "trillion".
+    pub fn english() -> Self {
+        Self::new(
+            vec![
+                ("a", 1.0),
+                ("an", 1.0),
+                ("one", 1.0),
+                ("two", 2.0),
+                ("three", 3.0),
+                ("four", 4.0),
+                ("five", 5.0),
+                ("six", 6.0),
+                ("seven", 7.0),
+                ("eight", 8.0),
+                ("nine", 9.0),
+                ("ten", 10.0),
+                ("eleven", 11.0),
+                ("twelve", 12.0),
+                ("twenty", 20.0),
+                ("thirty", 30.0),
+                ("forty", 40.0),
+                ("fifty", 50.0),
+                ("sixty", 60.0),
+                ("seventy", 70.0),
+                ("eighty", 80.0),
+                ("ninety", 90.0),
+            ],
+            vec![
+                ("dozen", 12.0),
+                ("hundred", 100.0),
+                ("thousand", 1_000.0),
+                ("million", 1_000_000.0),
+                ("billion", 1_000_000_000.0),
+                ("trillion", 1_000_000_000_000.0),
+            ],
+        )
+    }
+
+    fn lookup(words: &[(String, f64)], text: &str) -> Option<f64> {
+        let text = text.to_lowercase();
+        words
+            .iter()
+            .find(|(word, _)| *word == text)
+            .map(|(_, value)| *value)
+    }
+
+    /// Extends `number_sel` with all the scale words following it
+    fn match_scales(&self, number_sel: LLSelection, mut value: f64) -> Option<(LLSelection, f64)> {
+        let mut selection = number_sel;
+        let mut has_scale = false;
+
+        while let Some((scale_sel, scale)) = selection
+            .match_first_forwards(&x::whitespace())
+            .and_then(|(space_sel, _)| {
+                let (scale_sel, (_, text)) = space_sel
+                    .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))?;
+                Some((scale_sel, Self::lookup(&self.scales, text)?))
+            })
+        {
+            value *= scale;
+            has_scale = true;
+            selection = scale_sel;
+        }
+
+        if has_scale {
+            Some((selection, value))
+        } else {
+            None
+        }
+    }
+}
+
+impl Resolver for ScaledNumberResolver {
+    type Attr = Number;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let amounts = selection
+            .find_by(&x::attr::<Amount>())
+            .into_iter()
+            .filter_map(|(amount_sel, amount)| Some((amount_sel, amount.get_decimal().to_f64()?)));
+        let number_words = selection
+            .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .into_iter()
+            .filter_map(|(word_sel, (_, text))| {
+                Some((word_sel, Self::lookup(&self.number_words, text)?))
+            });
+
+        amounts
+            .chain(number_words)
+            .filter_map(|(number_sel, value)| self.match_scales(number_sel, value))
+            .map(|(scaled_sel, value)| scaled_sel.finish_with_attr(Number { value }))
+            .collect()
+    }
+}
@@ -0,0 +1,98 @@
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::{Amount, Number};
+
+/// Recognizes numbers written in scientific notation, with an exponent
+/// either as `1.5e10` and `2.3E-4`, or as `6.02 × 10^23` (also spaced out
+/// like `6.02 × 10 ^ 23`, and with `x` for `×`).
+///
+/// Builds on the [Amount] layer, so run [crate::AmountResolver] first.
+#[derive(Default)]
+pub struct ScientificNotationResolver(());
+
+impl ScientificNotationResolver {
+    /// `selection` extended over the whitespace following it, if any
+    fn skip_space(selection: LLSelection) -> LLSelection {
+        selection
+            .match_first_forwards(&x::whitespace())
+            .map(|(space_sel, _)| space_sel)
+            .unwrap_or(selection)
+    }
+
+    /// A signed integer exponent like `10`, `-4` or `+3` following `selection`
+    fn match_exponent(selection: &LLSelection) -> Option<(LLSelection, i32)> {
+        let (sign_sel, sign) = match selection.match_first_forwards(&x::token_has_any(&['-', '+']))
+        {
+            Some((sign_sel, sign)) => (sign_sel, if *sign == '-' { -1 } else { 1 }),
+            None => (selection.clone(), 1),
+        };
+        let (exponent_sel, exponent) = sign_sel.match_first_forwards(&x::attr::<Amount>())?;
+        let exponent = exponent.get_decimal();
+
+        if exponent.fract().is_zero() {
+            Some((exponent_sel, sign * exponent.to_i32()?))
+        } else {
+            None
+        }
+    }
+
+    /// The `e10` of `1.5e10`, with no whitespace in between
+    fn match_e(amount_sel: &LLSelection) -> Option<(LLSelection, i32)> {
+        let (e_sel, (_, text)) = amount_sel
+            .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))?;
+
+        if text == "e" || text == "E" {
+            Self::match_exponent(&e_sel)
+        } else {
+            None
+        }
+    }
+
+    /// The ` × 10^23` of `6.02 × 10^23`
+    fn match_times_ten(amount_sel: &LLSelection) -> Option<(LLSelection, i32)> {
+        let times_sel = Self::skip_space(amount_sel.clone());
+        let (times_sel, _) = times_sel
+            .match_first_forwards(&x::token_has_any(&['×']))
+            .or_else(|| {
+                let (word_sel, (_, text)) = times_sel
+                    .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))?;
+                if text == "x" {
+                    Some((word_sel, &'x'))
+                } else {
+                    None
+                }
+            })?;
+
+        let ten_sel = Self::skip_space(times_sel);
+        let (ten_sel, ten) = ten_sel.match_first_forwards(&x::attr::<Amount>())?;
+        if *ten.get_decimal() != 10.into() {
+            return None;
+        }
+
+        let (caret_sel, _) =
+            Self::skip_space(ten_sel).match_first_forwards(&x::token_has_any(&['^']))?;
+        Self::match_exponent(&Self::skip_space(caret_sel))
+    }
+}
+
+impl Resolver for ScientificNotationResolver {
+    type Attr = Number;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::attr::<Amount>())
+            .into_iter()
+            .filter_map(|(amount_sel, amount)| {
+                let (number_sel, exponent) =
+                    Self::match_e(&amount_sel).or_else(|| Self::match_times_ten(&amount_sel))?;
+                // parsed rather than multiplied, to get the closest `f64`
+                let value = format!("{}e{}", amount.get_decimal(), exponent)
+                    .parse()
+                    .ok()?;
+
+                Some(number_sel.finish_with_attr(Number { value }))
+            })
+            .collect()
+    }
+}
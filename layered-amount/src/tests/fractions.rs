@@ -0,0 +1,56 @@
+use crate::{Fraction, FractionResolver};
+use layered_nlp::{create_line_from_input_tokens, InputToken, LLLine, LLLineDisplay};
+
+fn test_setup(sentence: &'static str) -> LLLine {
+    create_line_from_input_tokens(
+        vec![InputToken::text(sentence.to_string(), Vec::new())],
+        |text| text.encode_utf16().count(),
+    )
+}
+
+#[test]
+fn test_fractions() {
+    let ll_line =
+        test_setup("Add 3/4 cup of flour, one half of the sugar and a quarter of the milk.")
+            .run(&FractionResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Fraction>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Add     3  /  4     cup     of     flour  ,     one     half     of     the     sugar     and     a     quarter     of     the     milk  .
+            ╰─────╯Fraction { numerator: 3, denominator: 4 }
+                                                    ╰──────────╯Fraction { numerator: 1, denominator: 2 }
+                                                                                                      ╰───────────╯Fraction { numerator: 1, denominator: 4 }
+    "###);
+}
+
+#[test]
+fn test_mixed_fractions() {
+    let ll_line = test_setup("It took two and a half hours, or 1 and 3/4 if you ask him, not 2/0.")
+        .run(&FractionResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Fraction>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    It     took     two     and     a     half     hours  ,     or     1     and     3  /  4     if     you     ask     him  ,     not     2  /  0  .
+                    ╰────────────────────────╯Fraction { numerator: 5, denominator: 2 }
+                                                                       ╰───────────────────╯Fraction { numerator: 7, denominator: 4 }
+    "###);
+}
+
+#[test]
+fn test_mixed_fraction_overflow() {
+    let ll_line = test_setup("18446744073709551615 and a half, or 9223372036854775807 and a half")
+        .run(&FractionResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Fraction>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    18446744073709551615     and     a     half  ,     or     9223372036854775807     and     a     half
+                                     ╰────────╯Fraction { numerator: 1, denominator: 2 }
+                                                              ╰────────────────────────────────────────╯Fraction { numerator: 18446744073709551615, denominator: 2 }
+    "###);
+}
@@ -0,0 +1,43 @@
+use crate::{AmountResolver, Number, ScaledNumberResolver};
+use layered_nlp::{create_line_from_input_tokens, InputToken, LLLine, LLLineDisplay};
+
+fn test_setup(sentence: &'static str) -> LLLine {
+    create_line_from_input_tokens(
+        vec![InputToken::text(sentence.to_string(), Vec::new())],
+        |text| text.encode_utf16().count(),
+    )
+}
+
+#[test]
+fn test_scaled_numbers() {
+    let ll_line =
+        test_setup("Raised 2 million, then 3.5 billion from a dozen investors and 40 more.")
+            .run(&AmountResolver::english())
+            .run(&ScaledNumberResolver::english());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Number>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Raised     2     million  ,     then     3  .  5     billion     from     a     dozen     investors     and     40     more  .
+               ╰───────────╯Number { value: 2000000.0 }
+                                             ╰─────────────────╯Number { value: 3500000000.0 }
+                                                                              ╰─────────╯Number { value: 12.0 }
+    "###);
+}
+
+#[test]
+fn test_chained_scales() {
+    let ll_line = test_setup("About two hundred thousand people, or 1.2 thousand million.")
+        .run(&AmountResolver::english())
+        .run(&ScaledNumberResolver::english());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Number>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    About     two     hundred     thousand     people  ,     or     1  .  2     thousand     million  .
+                                                                    ╰──────────────────────────────╯Number { value: 1200000000.0 }
+              ╰──────────────────────────╯Number { value: 200000.0 }
+    "###);
+}
@@ -0,0 +1,43 @@
+use crate::{AmountResolver, Number, ScientificNotationResolver};
+use layered_nlp::{create_line_from_input_tokens, InputToken, LLLine, LLLineDisplay};
+
+fn test_setup(sentence: &'static str) -> LLLine {
+    create_line_from_input_tokens(
+        vec![InputToken::text(sentence.to_string(), Vec::new())],
+        |text| text.encode_utf16().count(),
+    )
+}
+
+#[test]
+fn test_exponent() {
+    let ll_line = test_setup("Between 1.5e10 and 2.3E-4, but not 7 e 5 or 1.5e0.5")
+        .run(&AmountResolver::english())
+        .run(&ScientificNotationResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Number>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Between     1  .  5  e  10     and     2  .  3  E  -  4  ,     but     not     7     e     5     or     1  .  5  e  0  .  5
+                ╰────────────╯Number { value: 15000000000.0 }
+                                           ╰──────────────╯Number { value: 0.00023 }
+    "###);
+}
+
+#[test]
+fn test_times_ten() {
+    let ll_line =
+        test_setup("About 6.02 × 10^23 atoms, 6.02 × 10 ^ 23 again, 4 x 10^-3, not 2 × 3^4")
+            .run(&AmountResolver::english())
+            .run(&ScientificNotationResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Number>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    About     6  .  02     ×     10  ^  23     atoms  ,     6  .  02     ×     10     ^     23     again  ,     4     x     10  ^  -  3  ,     not     2     ×     3  ^  4
+              ╰──────────────────────────╯Number { value: 6.02e23 }
+                                                            ╰────────────────────────────────╯Number { value: 6.02e23 }
+                                                                                                                ╰─────────────────────╯Number { value: 0.004 }
+    "###);
+}
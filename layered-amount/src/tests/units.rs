@@ -0,0 +1,68 @@
+use crate::{
+    AmountResolver, ConversionCandidate, Measurement, MeasurementResolver, UnitMismatchResolver,
+};
+use layered_nlp::{create_line_from_input_tokens, InputToken, LLLine, LLLineDisplay};
+
+fn test_setup(sentence: &'static str) -> LLLine {
+    create_line_from_input_tokens(
+        vec![InputToken::text(sentence.to_string(), Vec::new())],
+        |text| text.encode_utf16().count(),
+    )
+}
+
+#[test]
+fn test_measurements() {
+    let ll_line = test_setup("Ran 5 km in 30min, carrying 2.5 kg and 3 apples")
+        .run(&AmountResolver::english())
+        .run(&MeasurementResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Measurement>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Ran     5     km     in     30  min  ,     carrying     2  .  5     kg     and     3     apples
+            ╰──────╯Measurement { value: 5.0, unit: "km" }
+                                ╰─────╯Measurement { value: 30.0, unit: "min" }
+                                                            ╰────────────╯Measurement { value: 2.5, unit: "kg" }
+    "###);
+}
+
+#[test]
+fn test_unit_mismatch() {
+    let ll_line = test_setup("Walk 5 km and 3000 m in 2 h, with 2 kg then 3 kg")
+        .run(&AmountResolver::english())
+        .run(&MeasurementResolver::default())
+        .run(&UnitMismatchResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<ConversionCandidate>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Walk     5     km     and     3000     m     in     2     h  ,     with     2     kg     then     3     kg
+             ╰─────────────────────────────╯ConversionCandidate { dimension: "length", ranges: [(2, 4), (8, 10)] }
+    "###);
+}
+
+#[test]
+fn test_unit_mismatch_around_custom_token() {
+    // the custom token has no `TextTag`
+    let ll_line = create_line_from_input_tokens(
+        vec![
+            InputToken::text("Walk 5 km and ".to_string(), Vec::new()),
+            InputToken::custom(1, Vec::new()),
+            InputToken::text(" 3000 m".to_string(), Vec::new()),
+        ],
+        |text| text.encode_utf16().count(),
+    )
+    .run(&AmountResolver::english())
+    .run(&MeasurementResolver::default())
+    .run(&UnitMismatchResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<ConversionCandidate>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Walk     5     km     and     <>     3000     m
+             ╰────────────────────────────────────╯ConversionCandidate { dimension: "length", ranges: [(2, 4), (10, 12)] }
+    "###);
+}
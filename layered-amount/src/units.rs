@@ -0,0 +1,177 @@
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::Amount;
+
+/// Classifies units by what they measure, like `km` and `m` which are both
+/// a `length`.
+#[derive(Clone, Debug)]
+pub struct UnitDimensions {
+    /// Units, as written, and their dimension
+    units: Vec<(String, String)>,
+}
+
+impl UnitDimensions {
+    pub fn new(units: &[(&str, &str)]) -> Self {
+        UnitDimensions {
+            units: units
+                .iter()
+                .map(|(unit, dimension)| (unit.to_string(), dimension.to_string()))
+                .collect(),
+        }
+    }
+
+    /// The dimension of `unit`, like `length` for `km`
+    pub fn dimension(&self, unit: &str) -> Option<&str> {
+        self.units
+            .iter()
+            .find(|(known_unit, _)| known_unit == unit)
+            .map(|(_, dimension)| dimension.as_str())
+    }
+}
+
+impl Default for UnitDimensions {
+    /// Abbreviated metric and imperial units of `length`, `mass`, `volume`
+    /// and `time`.
+    fn default() -> Self {
+        UnitDimensions::new(&[
+            ("mm", "length"),
+            ("cm", "length"),
+            ("m", "length"),
+            ("km", "length"),
+            ("ft", "length"),
+            ("yd", "length"),
+            ("mi", "length"),
+            // no `in`, there are too many `2 in 3`
+            ("mg", "mass"),
+            ("g", "mass"),
+            ("kg", "mass"),
+            ("oz", "mass"),
+            ("lb", "mass"),
+            ("ml", "volume"),
+            ("cl", "volume"),
+            ("l", "volume"),
+            ("L", "volume"),
+            ("gal", "volume"),
+            ("ms", "time"),
+            ("s", "time"),
+            ("min", "time"),
+            ("h", "time"),
+        ])
+    }
+}
+
+/// A number and its unit, like `5 km`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Measurement {
+    pub value: f64,
+    pub unit: String,
+}
+
+/// Recognizes an [Amount] followed by a unit of its [UnitDimensions], like
+/// `5 km` or `5km`.
+///
+/// Run [crate::AmountResolver] first.
+#[derive(Default)]
+pub struct MeasurementResolver {
+    dimensions: UnitDimensions,
+}
+
+impl MeasurementResolver {
+    pub fn new(dimensions: UnitDimensions) -> Self {
+        MeasurementResolver { dimensions }
+    }
+
+    /// The known unit following `amount_sel`
+    fn match_unit(&self, amount_sel: &LLSelection) -> Option<(LLSelection, String)> {
+        let space_sel = amount_sel
+            .match_first_forwards(&x::whitespace())
+            .map(|(space_sel, _)| space_sel)
+            .unwrap_or_else(|| amount_sel.clone());
+        let (unit_sel, (_, text)) = space_sel
+            .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))?;
+        self.dimensions.dimension(text)?;
+
+        Some((unit_sel, text.to_string()))
+    }
+}
+
+impl Resolver for MeasurementResolver {
+    type Attr = Measurement;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::attr::<Amount>())
+            .into_iter()
+            .filter_map(|(amount_sel, amount)| {
+                let (measurement_sel, unit) = self.match_unit(&amount_sel)?;
+
+                Some(measurement_sel.finish_with_attr(Measurement {
+                    value: amount.get_decimal().to_f64()?,
+                    unit,
+                }))
+            })
+            .collect()
+    }
+}
+
+/// Measurements of a single dimension written in different units, which
+/// need converting to be compared, like `5 km and 3000 m`.
+///
+/// Assigned from the start of the first measurement to the end of the last.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConversionCandidate {
+    pub dimension: String,
+    /// (start, end) token indexes of each [Measurement] of the dimension
+    pub ranges: Vec<(usize, usize)>,
+}
+
+/// Groups the [Measurement] layer by dimension, and flags the dimensions
+/// measured with more than one unit with a [ConversionCandidate].
+///
+/// Run [MeasurementResolver] first.
+#[derive(Default)]
+pub struct UnitMismatchResolver {
+    dimensions: UnitDimensions,
+}
+
+impl UnitMismatchResolver {
+    pub fn new(dimensions: UnitDimensions) -> Self {
+        UnitMismatchResolver { dimensions }
+    }
+}
+
+impl Resolver for UnitMismatchResolver {
+    type Attr = ConversionCandidate;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        // in order of their first measurement
+        let mut groups: Vec<(&str, Vec<(LLSelection, &Measurement)>)> = Vec::new();
+        let measurements = selection.find_by(&x::attr::<Measurement>());
+
+        for (measurement_sel, measurement) in measurements.iter() {
+            let dimension = match self.dimensions.dimension(&measurement.unit) {
+                Some(dimension) => dimension,
+                None => continue,
+            };
+
+            match groups.iter_mut().find(|(group, _)| *group == dimension) {
+                Some((_, group)) => group.push((measurement_sel.clone(), measurement)),
+                None => groups.push((dimension, vec![(measurement_sel.clone(), measurement)])),
+            }
+        }
+
+        groups
+            .into_iter()
+            .filter(|(_, group)| group.iter().any(|(_, m)| m.unit != group[0].1.unit))
+            .map(|(dimension, group)| {
+                let candidate_sel = group[0].0.extend_to(&group[group.len() - 1].0);
+
+                candidate_sel.finish_with_attr(ConversionCandidate {
+                    dimension: dimension.to_string(),
+                    ranges: group.iter().map(|(sel, _)| sel.token_range()).collect(),
+                })
+            })
+            .collect()
+    }
+}
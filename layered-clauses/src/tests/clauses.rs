@@ -23,7 +23,7 @@ fn test_clauses() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     When     it     rains  ,     then     it     pours  .
     ╰──╯ConditionStart
                                  ╰──╯Then
@@ -46,7 +46,7 @@ fn test_short_clauses() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     When     it     rains  ,     run  !
     ╰──╯ConditionStart
              ╰──────────╯Condition
@@ -70,7 +70,7 @@ fn test_clauses_comma() {
     ll_line_display.include::<Clause>();
     ll_line_display.include::<Tag>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     If     it     is     raining  ,     open     your     umbrella  .
     ╰╯ConditionStart
            ╰───────────────────╯Condition
@@ -105,7 +105,7 @@ fn tired() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     Si     tu     es     fatigué  ,     va     te     coucher  .
     ╰╯ConditionStart
            ╰───────────────────╯Condition
@@ -123,7 +123,7 @@ fn tired_rev() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     Va     te     coucher     si     tu     es     fatigué  .
                               ╰╯ConditionStart
     ╰───────────────────╯LeadingEffect
@@ -146,7 +146,7 @@ fn rain() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     If     it     is     raining     then     open     the     umbrella     and     close     the     garage     and     the     door  .
     ╰╯ConditionStart
                                      ╰──╯Then
@@ -175,7 +175,7 @@ fn rain_rev() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     Open     the     umbrella     and     close     the     garage     and     the     door     if     it     is     raining  .
                                   ╰─╯And
                                                                        ╰─╯And
@@ -202,7 +202,7 @@ fn extra_rain() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     Open     the     umbrella     if     it     is     raining     and     not     too     windy  .
                                   ╰╯ConditionStart
                                                                    ╰─╯And
@@ -227,7 +227,7 @@ fn no_keyword() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     Open     the     umbrella  .
     ╰───────────────────────╯Independent
     "###
@@ -0,0 +1,47 @@
+use crate::Tag;
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, Sentence, TextTag};
+
+/// Sentence giving an order or instruction, like "Open the door."
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Imperative;
+
+/// Reads the [Sentence] and [Tag] layers, so run [layered_nlp::SentenceResolver]
+/// and [crate::POSTagResolver] first.
+///
+/// A sentence is [Imperative] when its first word:
+///  * can be a [Tag::Verb], and
+///  * can't be a [Tag::Pronoun] or [Tag::Determiner], which would be the start
+///    of an explicit subject (`You open the door`, `The door opens`).
+///
+/// Words are tagged with every part of speech they may have, so this is only
+/// a heuristic: a leading noun which may also be a verb (`Dogs bark`) is
+/// wrongly recognized.
+#[derive(Default)]
+pub struct ImperativeResolver(());
+
+impl Resolver for ImperativeResolver {
+    type Attr = Imperative;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::attr::<Sentence>())
+            .into_iter()
+            .filter(|(sentence_sel, _)| {
+                sentence_sel
+                    .find_first_by(&x::attr_eq(&TextTag::WORD))
+                    .is_some_and(|(word_sel, _)| {
+                        let tags: Vec<&Tag> = word_sel
+                            .find_by(&x::attr::<Tag>())
+                            .into_iter()
+                            .map(|(_, tag)| tag)
+                            .collect();
+
+                        tags.contains(&&Tag::Verb)
+                            && !tags.contains(&&Tag::Pronoun)
+                            && !tags.contains(&&Tag::Determiner)
+                    })
+            })
+            .map(|(sentence_sel, _)| sentence_sel.finish_with_attr(Imperative))
+            .collect()
+    }
+}
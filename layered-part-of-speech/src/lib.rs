@@ -3,6 +3,9 @@
     issue_tracker_base_url = "https://github.com/storyscript/layered-nlp/issues/"
 )]
 
+mod imperative;
+
+pub use imperative::{Imperative, ImperativeResolver};
 pub use wiktionary_part_of_speech_extract::Tag;
 
 use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
@@ -28,3 +31,8 @@ impl Resolver for POSTagResolver {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    mod imperative;
+}
@@ -0,0 +1,33 @@
+use crate::{Imperative, ImperativeResolver, POSTagResolver};
+use layered_nlp::{
+    create_line_from_input_tokens, InputToken, LLLine, LLLineDisplay, Sentence, SentenceResolver,
+};
+
+fn test_setup(sentence: &'static str) -> LLLine {
+    create_line_from_input_tokens(
+        vec![InputToken::text(sentence.to_string(), Vec::new())],
+        |text| text.encode_utf16().count(),
+    )
+}
+
+#[test]
+fn test_imperative() {
+    let ll_line = test_setup("Open the door. You open the door. The door opens! \"Send it now\"")
+        .run(&SentenceResolver::default())
+        .run(&POSTagResolver::default())
+        .run(&ImperativeResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Sentence>();
+    ll_line_display.include::<Imperative>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Open     the     door  .     You     open     the     door  .     The     door     opens  !     "  Send     it     now  "
+    ╰──────────────────────╯Sentence
+                                 ╰──────────────────────────────╯Sentence
+                                                                      ╰───────────────────────╯Sentence
+                                                                                                    ╰───────────────────────╯Sentence
+    ╰──────────────────────╯Imperative
+                                                                                                    ╰───────────────────────╯Imperative
+    "###);
+}
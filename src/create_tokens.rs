@@ -1,6 +1,6 @@
 mod get_word_tag;
 
-use crate::ll_line::{LLLine, LLToken, LToken, TextTag};
+use crate::ll_line::{LLLine, LLToken, LToken, TextTag, TokenValue};
 use crate::type_bucket::AnyAttribute;
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -17,6 +17,14 @@ pub enum InputToken {
         /// Custom attributes
         attrs: Vec<AnyAttribute>,
     },
+    /// A token carrying a typed value, see [TokenValue]
+    Value {
+        /// Position relative size
+        size: usize,
+        value: TokenValue,
+        /// Custom attributes
+        attrs: Vec<AnyAttribute>,
+    },
 }
 
 impl InputToken {
@@ -28,10 +36,15 @@ impl InputToken {
         InputToken::Custom { size, attrs }
     }
 
-    pub fn add_attr<T: 'static + std::fmt::Debug>(&mut self, value: T) {
+    pub fn value(size: usize, value: TokenValue, attrs: Vec<AnyAttribute>) -> Self {
+        InputToken::Value { size, value, attrs }
+    }
+
+    pub fn add_attr<T: 'static + std::fmt::Debug + Clone + Send>(&mut self, value: T) {
         match self {
             InputToken::Text { attrs, .. } => attrs.push(AnyAttribute::new(value)),
             InputToken::Custom { attrs, .. } => attrs.push(AnyAttribute::new(value)),
+            InputToken::Value { attrs, .. } => attrs.push(AnyAttribute::new(value)),
         }
     }
 }
@@ -45,24 +58,52 @@ where
     create_line_from_input_tokens(input, get_text_size)
 }
 
+/// Splits the `InputToken`s into a [LLLine] and generates `TextTag`s.
+///
+/// Does not panic on any text: an empty text produces no tokens, and its
+/// attributes are dropped since there is no range to assign them to.
 pub fn create_line_from_input_tokens<F>(input: Vec<InputToken>, get_text_size: F) -> LLLine
 where
     F: Fn(&str) -> usize,
+{
+    create_line_with_tokenizer(input, get_text_size, &DefaultTokenizer::default())
+}
+
+/// Like [create_line_from_input_tokens], splitting the texts with `tokenizer`.
+pub fn create_line_with_tokenizer<F, T>(
+    input: Vec<InputToken>,
+    get_text_size: F,
+    tokenizer: &T,
+) -> LLLine
+where
+    F: Fn(&str) -> usize,
+    T: Tokenizer,
 {
     let mut start_idx_end_idx_attributes: Vec<(usize, usize, Vec<AnyAttribute>)> = Vec::new();
     let mut lltokens: Vec<LLToken> = Vec::new();
     let mut current_size = 0;
 
     for (ltokens, attrs) in input.into_iter().map(|input_token| match input_token {
-        InputToken::Text { text, attrs } => {
-            (create_tokens_for_string(&text, &get_text_size), attrs)
+        InputToken::Text { text, attrs } => (
+            tokenizer
+                .tokenize(&text)
+                .into_iter()
+                .map(|(text, tag)| {
+                    let size = get_text_size(&text);
+                    (LToken::Text(text, tag), size)
+                })
+                .collect(),
+            attrs,
+        ),
+        InputToken::Custom { size, attrs } => {
+            (vec![(LToken::Value(TokenValue::default()), size)], attrs)
         }
-        InputToken::Custom { size, attrs } => (vec![(LToken::Value, size)], attrs),
+        InputToken::Value { size, value, attrs } => (vec![(LToken::Value(value), size)], attrs),
     }) {
-        assert!(
-            !ltokens.is_empty(),
-            "Cannot create a LLLine from empty String"
-        );
+        // An empty text has no tokens to carry its attributes
+        if ltokens.is_empty() {
+            continue;
+        }
 
         let from_idx = lltokens.len();
         for (ltoken, size) in ltokens {
@@ -89,121 +130,135 @@ where
     ll_line
 }
 
-// helper for [create_tokens]
-fn create_tokens_for_string<F>(input: &str, get_text_size: F) -> Vec<(LToken, usize)>
-where
-    F: Fn(&str) -> usize,
-{
-    // `fold` because we end up splitting more than just unicode word boundaries
-    input
-        .split_word_bounds()
-        .fold(Vec::new(), |mut ltokens, unicode_word| {
-            // Split apart digit word boundaries, because unicode `split_word_bounds` will group digits and commas and points together
-            // such as "12,3" and "10.0". We need these to be split up further into ["12", ",", "3"] and ["10", ".", "0"] respectively.
-            // http://www.unicode.org/reports/tr29/#Word_Boundaries
-            // if \d+[,\.a-zA-Z]\d+ or more repeats (3 is minimum)
-            if unicode_word.chars().next().unwrap().is_ascii_digit() {
-                // make assumtions about the length of every char being 1
-                assert!(
-                    unicode_word.is_ascii(),
-                    "Unexpected non-ascii digit word boundary: {}",
-                    unicode_word
-                );
-
-                let mut collected_digits = String::new();
-                let mut collected_letters = String::new();
-
-                // using a macro since pulling this out into a closure or function would be very verbose
-                // as you'd have to pass references to collected_digits, get_text_size, ltokens
-                macro_rules! insert_collected_digits {
-                    () => {
-                        if collected_digits.len() > 0 {
-                            let size = get_text_size(&collected_digits);
-                            ltokens.push((
-                                LToken::Text(std::mem::take(&mut collected_digits), TextTag::NATN),
-                                size,
-                            ));
+/// Splits the text of [InputToken::Text] into tokens, for
+/// [create_line_with_tokenizer].
+///
+/// The lengths of the tokens are measured on their text, so the texts of the
+/// tokens put together must be the text given. A token of a single char gets
+/// the `char` attribute, whatever the tokenizer.
+pub trait Tokenizer {
+    /// The pieces of `text` in order, and their [TextTag]
+    fn tokenize(&self, text: &str) -> Vec<(String, TextTag)>;
+}
+
+/// The tokenizer of [create_line_from_input_tokens], splitting on unicode
+/// word boundaries, then digits from letters and words on apostrophes.
+#[derive(Default)]
+pub struct DefaultTokenizer(());
+
+impl Tokenizer for DefaultTokenizer {
+    fn tokenize(&self, input: &str) -> Vec<(String, TextTag)> {
+        // `fold` because we end up splitting more than just unicode word boundaries
+        input
+            .split_word_bounds()
+            .fold(Vec::new(), |mut tokens, unicode_word| {
+                // Split apart digit word boundaries, because unicode `split_word_bounds` will group digits and commas and points together
+                // such as "12,3" and "10.0". We need these to be split up further into ["12", ",", "3"] and ["10", ".", "0"] respectively.
+                // http://www.unicode.org/reports/tr29/#Word_Boundaries
+                // if \d+[,\.a-zA-Z]\d+ or more repeats (3 is minimum)
+                // Non-ascii chars may follow the digit (as in "1é" or "1٣"), each becoming its own piece
+                if unicode_word.starts_with(|ch: char| ch.is_ascii_digit()) {
+                    let mut collected_digits = String::new();
+                    let mut collected_letters = String::new();
+
+                    // using a macro since pulling this out into a closure or function would be very verbose
+                    // as you'd have to pass references to collected_digits, tokens
+                    macro_rules! insert_collected_digits {
+                        () => {
+                            if collected_digits.len() > 0 {
+                                tokens.push((std::mem::take(&mut collected_digits), TextTag::NATN));
+                            }
+                        };
+                    }
+
+                    macro_rules! insert_collected_letters {
+                        () => {
+                            if collected_letters.len() > 0 {
+                                tokens
+                                    .push((std::mem::take(&mut collected_letters), TextTag::WORD));
+                            }
+                        };
+                    }
+
+                    for ch in unicode_word.chars() {
+                        if ch.is_ascii_digit() {
+                            insert_collected_letters!();
+                            collected_digits.push(ch);
+                        } else if ch.is_alphabetic() {
+                            insert_collected_digits!();
+                            collected_letters.push(ch);
+                        } else {
+                            insert_collected_letters!();
+                            insert_collected_digits!();
+                            tokens.push((String::from(ch), TextTag::PUNC));
                         }
-                    };
-                }
+                    }
 
-                macro_rules! insert_collected_letters {
-                    () => {
-                        if collected_letters.len() > 0 {
-                            let size = get_text_size(&collected_letters);
-                            ltokens.push((
-                                LToken::Text(std::mem::take(&mut collected_letters), TextTag::WORD),
-                                size,
+                    insert_collected_letters!();
+                    insert_collected_digits!();
+                } else {
+                    let mut last_apostrophe_index = 0;
+
+                    unicode_word.match_indices('\'').for_each(|(index, _)| {
+                        let piece = &unicode_word[last_apostrophe_index..index];
+                        if !piece.is_empty() {
+                            tokens.push((
+                                piece.to_string(),
+                                get_word_tag::get_unicode_word_tag(piece),
                             ));
                         }
-                    };
-                }
 
-                for ch in unicode_word.chars() {
-                    if ch.is_ascii_digit() {
-                        insert_collected_letters!();
-                        collected_digits.push(ch);
-                    } else if ch.is_alphabetic() {
-                        insert_collected_digits!();
-                        collected_letters.push(ch);
-                    } else {
-                        insert_collected_letters!();
-                        insert_collected_digits!();
-                        let text = String::from(ch);
-                        let size = get_text_size(&text);
-                        ltokens.push((LToken::Text(text, TextTag::PUNC), size));
-                    }
-                }
+                        tokens.push(("'".to_string(), get_word_tag::get_unicode_word_tag("'")));
 
-                insert_collected_letters!();
-                insert_collected_digits!();
-            } else {
-                let mut last_apostrophe_index = 0;
-
-                unicode_word.match_indices('\'').for_each(|(index, _)| {
-                    if !(last_apostrophe_index..index).is_empty() {
-                        ltokens.push((
-                            LToken::Text(
-                                unicode_word[last_apostrophe_index..index].to_string(),
-                                get_word_tag::get_unicode_word_tag(
-                                    &unicode_word[last_apostrophe_index..index],
-                                ),
-                            ),
-                            get_text_size(&unicode_word[last_apostrophe_index..index]),
-                        ));
-                    }
+                        last_apostrophe_index = index + 1;
+                    });
 
-                    if last_apostrophe_index == 0 {
-                        ltokens.push((
-                            LToken::Text("'".to_string(), get_word_tag::get_unicode_word_tag("'")),
-                            get_text_size("'"),
-                        ));
+                    let piece = &unicode_word[last_apostrophe_index..];
+                    if !piece.is_empty() {
+                        tokens.push((piece.to_string(), get_word_tag::get_unicode_word_tag(piece)));
                     }
+                }
+
+                tokens
+            })
+    }
+}
 
-                    last_apostrophe_index = index + 1;
-                });
-
-                if !unicode_word[last_apostrophe_index..].is_empty() {
-                    ltokens.push((
-                        LToken::Text(
-                            unicode_word[last_apostrophe_index..].to_string(),
-                            get_word_tag::get_unicode_word_tag(
-                                &unicode_word[last_apostrophe_index..],
-                            ),
-                        ),
-                        get_text_size(&unicode_word[last_apostrophe_index..]),
-                    ));
+/// Like [DefaultTokenizer], keeping contractions like `don't` and hyphenated
+/// compounds like `state-of-the-art` as single [TextTag::WORD] tokens.
+#[derive(Default)]
+pub struct CompoundWordTokenizer(());
+
+impl Tokenizer for CompoundWordTokenizer {
+    fn tokenize(&self, text: &str) -> Vec<(String, TextTag)> {
+        let mut tokens: Vec<(String, TextTag)> = Vec::new();
+
+        for (piece, tag) in DefaultTokenizer::default().tokenize(text) {
+            // a word, a joiner and this word are merged
+            if let [.., (_, TextTag::WORD), (joiner, _)] = tokens.as_slice() {
+                if tag == TextTag::WORD && (joiner == "'" || joiner == "-") {
+                    let (joiner, _) = tokens.pop().expect("has joiner");
+                    let (word, _) = tokens.last_mut().expect("has word");
+                    word.push_str(&joiner);
+                    word.push_str(&piece);
+                    continue;
                 }
             }
 
-            ltokens
-        })
+            tokens.push((piece, tag));
+        }
+
+        tokens
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::{create_line_from_input_tokens, InputToken};
-    use crate::ll_line::LLLineDisplay;
+    use super::{
+        create_line_from_input_tokens, create_line_with_tokenizer, CompoundWordTokenizer,
+        DefaultTokenizer, InputToken, Tokenizer,
+    };
+    use crate::ll_line::{LLLineDisplay, LToken};
     use crate::type_bucket::AnyAttribute;
 
     #[derive(Debug, Clone)]
@@ -213,6 +268,7 @@ mod test {
     }
 
     #[derive(Debug, Clone)]
+    #[allow(dead_code)]
     struct Link {
         href: String,
     }
@@ -243,7 +299,7 @@ mod test {
         let mut ll_line_display = LLLineDisplay::new(&ll_line);
         ll_line_display.include::<MarkKind>();
 
-        insta::assert_display_snapshot!(ll_line_display, @r###"
+        insta::assert_snapshot!(ll_line_display, @r###"
         Hello  ,     World  !
                      ╰───╯Italic
                      ╰───╯Bold
@@ -270,10 +326,154 @@ mod test {
         ll_line_display.include::<MarkKind>();
         ll_line_display.include::<Link>();
 
-        insta::assert_display_snapshot!(ll_line_display, @r###"
+        insta::assert_snapshot!(ll_line_display, @r###"
         name  @  example.com
         ╰──────────────────╯Italic
         ╰──────────────────╯Link { href: "mailto:name@example.com" }
         "###);
     }
+
+    /// Inputs which used to panic, or look like they could
+    const TRICKY_INPUTS: &[&str] = &[
+        "",
+        " ",
+        "\0",
+        "\u{feff}",
+        "\r\n",
+        "\u{301}",
+        "e\u{301}\u{302}\u{303}\u{304}\u{305}\u{306}\u{307}\u{308}\u{309}\u{30a}",
+        "1\u{301}",
+        "1é",
+        "1٣",
+        "٣٣,٣",
+        "12,3.4e5",
+        "'",
+        "''",
+        "it's'",
+        "1'2",
+        "rock'n'roll",
+        "🏳️‍🌈 flag",
+        "ǅemal",
+        "東京 2020",
+    ];
+
+    #[test]
+    fn test_create_tokens_tricky_inputs() {
+        let combining_marks = format!("a{}", "\u{301}".repeat(10_000));
+
+        for input in TRICKY_INPUTS
+            .iter()
+            .copied()
+            .chain(std::iter::once(combining_marks.as_str()))
+        {
+            let ll_line = create_line_from_input_tokens(
+                vec![
+                    InputToken::text(input.to_string(), vec![AnyAttribute::new(MarkKind::Bold)]),
+                    InputToken::custom(0, vec![]),
+                    InputToken::text(input.to_string(), vec![]),
+                ],
+                |text| text.encode_utf16().count(),
+            );
+
+            let mut pos = 0;
+            let mut text = String::new();
+            for (idx, ll_token) in ll_line.ll_tokens().iter().enumerate() {
+                assert_eq!(ll_token.token_idx(), idx, "{:?}", input);
+                assert_eq!(ll_token.pos_starts_at(), pos, "{:?}", input);
+                pos = ll_token.pos_ends_at();
+                if let LToken::Text(token_text, _) = ll_token.token() {
+                    text.push_str(token_text);
+                }
+            }
+            // no text is lost nor added in tokenizing
+            assert_eq!(text, format!("{}{}", input, input), "{:?}", input);
+
+            let mut ll_line_display = LLLineDisplay::new(&ll_line);
+            ll_line_display.include::<MarkKind>();
+            let _ = ll_line_display.to_string();
+        }
+    }
+
+    #[test]
+    fn test_create_tokens_with_tokenizer() {
+        let input = || {
+            vec![InputToken::text(
+                "I don't use state-of-the-art tools".to_string(),
+                vec![],
+            )]
+        };
+        let ll_line = create_line_with_tokenizer(
+            input(),
+            |text| text.len(),
+            &CompoundWordTokenizer::default(),
+        );
+
+        let mut ll_line_display = LLLineDisplay::new(&ll_line);
+        ll_line_display.include::<crate::TextTag>();
+        insta::assert_snapshot!(ll_line_display, @r###"
+        I     don't     use     state-of-the-art     tools
+        ╰WORD
+           ╰SPACE
+              ╰───╯WORD
+                     ╰SPACE
+                        ╰─╯WORD
+                             ╰SPACE
+                                ╰──────────────╯WORD
+                                                  ╰SPACE
+                                                     ╰───╯WORD
+        "###);
+        let default_line = create_line_from_input_tokens(input(), |text| text.len());
+        insta::assert_snapshot!(LLLineDisplay::new(&default_line), @"I     don  '  t     use     state  -  of  -  the  -  art     tools");
+    }
+
+    #[test]
+    fn test_tokenizers_keep_text() {
+        let tokenizers: [&dyn Tokenizer; 2] = [
+            &DefaultTokenizer::default(),
+            &CompoundWordTokenizer::default(),
+        ];
+
+        for tokenizer in tokenizers {
+            for input in TRICKY_INPUTS
+                .iter()
+                .copied()
+                .chain(["I don't rock'n'roll, y'all''"])
+            {
+                let text: String = tokenizer
+                    .tokenize(input)
+                    .into_iter()
+                    .map(|(piece, _)| piece)
+                    .collect();
+                assert_eq!(text, input);
+            }
+        }
+    }
+
+    /// Splits every char apart
+    struct CharTokenizer;
+
+    impl Tokenizer for CharTokenizer {
+        fn tokenize(&self, text: &str) -> Vec<(String, crate::TextTag)> {
+            text.chars()
+                .map(|ch| (ch.to_string(), crate::TextTag::SYMB))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_create_tokens_with_custom_tokenizer() {
+        let ll_line = create_line_with_tokenizer(
+            vec![InputToken::text("aé!".to_string(), vec![])],
+            |text| text.chars().count(),
+            &CharTokenizer,
+        );
+
+        let positions: Vec<_> = ll_line
+            .ll_tokens()
+            .iter()
+            .map(|ll_token| (ll_token.pos_starts_at(), ll_token.pos_ends_at()))
+            .collect();
+        assert_eq!(positions, [(0, 1), (1, 2), (2, 3)]);
+        assert_eq!(ll_line.query::<char>().len(), 3);
+    }
 }
@@ -11,11 +11,11 @@ static IS_WORD: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\w[\w'-]*$").unwrap());
 static IS_SPACE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s+$").unwrap());
 
 pub(crate) fn get_unicode_word_tag(word: &str) -> TextTag {
-    if IS_SPACE.is_match(&word) {
+    if IS_SPACE.is_match(word) {
         TextTag::SPACE
-    } else if word.len() == 1 && PUNCTUATION.contains(&word.chars().next().unwrap()) {
+    } else if word.len() == 1 && word.starts_with(PUNCTUATION) {
         TextTag::PUNC
-    } else if IS_WORD.is_match(&word) {
+    } else if IS_WORD.is_match(word) {
         TextTag::WORD
     } else {
         TextTag::SYMB
@@ -13,22 +13,38 @@ mod type_id_to_many;
 mod tests;
 #[allow(deprecated)]
 pub use create_tokens::create_tokens;
-pub use create_tokens::{create_line_from_input_tokens, InputToken};
+pub use create_tokens::{
+    create_line_from_input_tokens, create_line_with_tokenizer, CompoundWordTokenizer,
+    DefaultTokenizer, InputToken, Tokenizer,
+};
 
-/// Simpler, less featureful version of [create_line_from_input_tokens] which uses utf8 counts as string length ([String::len]).
+/// Simpler, less featureful version of [create_line_from_input_tokens] which
+/// uses char counts as string length, so positions (like [LLToken::char_range]
+/// and the range of the matches of [LLLine::find]) are char offsets.
 ///
 /// Use [create_line_from_input_tokens] to specify custom string length
 /// calculation function and to supply custom predefined attributes or
 /// custom tokens.
 pub fn create_line_from_string<T: AsRef<str>>(input_string: T) -> LLLine {
     let token = InputToken::text(input_string.as_ref().to_string(), Vec::new());
-    create_line_from_input_tokens(vec![token], |s| s.len())
+    create_line_from_input_tokens(vec![token], |s| s.chars().count())
 }
 
+#[cfg(feature = "serde")]
+pub use ll_line::AttrRegistry;
 pub use ll_line::{
-    x, FinishWith, LLCursorAssignment, LLLine, LLLineDisplay, LLSelection, Resolver, TextTag,
+    assert_resolver_idempotent, resolver_fn, x, ByteRangeError, FinishWith, FnResolver,
+    LLCursorAssignment, LLLine, LLLineDisplay, LLSelection, LLToken, LToken, Metadata, Resolver,
+    ResolverPipeline, TextTag, TokenValue,
+};
+pub use resolvers::{
+    Attribution, Bool, BooleanResolver, Coordination, CoordinationResolver, Date, DateRange,
+    DateRangeResolver, DialogueAttributionResolver, Dimensions, DimensionsResolver, Echo, Emoticon,
+    EmoticonResolver, IpAddr, IpAddressResolver, KeyValue, KeyValueResolver,
+    NearbyRepetitionResolver, Quoted, QuotedResolver, Reference, ReferenceResolver, Salutation,
+    SalutationKind, SalutationResolver, Sentence, SentenceResolver, TextMatchAssignResolver,
+    Version, VersionResolver,
 };
-pub use resolvers::TextMatchAssignResolver;
 pub use type_bucket::AnyAttribute;
 
 /// Shorthand of [LLLineDisplay::new]
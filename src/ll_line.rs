@@ -1,24 +1,32 @@
 mod display;
 mod finish_with;
+#[cfg(feature = "serde")]
+mod json;
 mod ll_selection;
+mod pipeline;
 pub mod x;
 
 pub use finish_with::FinishWith;
+#[cfg(feature = "serde")]
+pub use json::AttrRegistry;
 pub use ll_selection::LLSelection;
+pub use pipeline::ResolverPipeline;
 
 use crate::type_bucket::{self, AnyAttribute};
 use crate::type_id_to_many::TypeIdToMany;
 pub use display::LLLineDisplay;
+use std::any::TypeId;
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::{self, Write};
 use std::iter::FromIterator;
-use std::{collections::HashMap, rc::Rc};
-pub use x::{Attr, AttrEq};
-use x::{XForwards, XMatch};
+use std::{marker::PhantomData, rc::Rc};
+use x::{XBackwards, XForwards, XMatch};
 
 /// [TextTag] is an attribute added at the beginning of every new line.
-/// 
+///
 /// Each piece of a line is sort of "tokenized" and each token is assigned a [TextTag] attribute.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TextTag {
     /// Natural number like `0`, `1200`, `0004`
     NATN,
@@ -30,32 +38,119 @@ pub enum TextTag {
     /// A combination of unicode whitespaces
     SPACE,
     /// A word as identified by unicode word recognition rules.
-    /// 
+    ///
     /// For example: `yello`, `Paris`, `don't`, `should've`
     WORD,
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LToken {
     Text(String, TextTag),
-    /// TODO: something more interesting
-    Value,
+    /// A token standing for a value rather than text, see [TokenValue]
+    Value(TokenValue),
 }
 
-#[derive(Debug)]
+/// The payload of a [LToken::Value], like a parsed date or an entity id put
+/// in the line before running resolvers.
+///
+/// The value is assigned to its token as an attribute, so matchers like
+/// [x::attr] find it by its type. In the text of the line, as in
+/// [LLLine::query], the token is replaced by its placeholder, empty by default.
+///
+/// With the `serde` feature, only the placeholder is serialized: the value
+/// is saved as an attribute by `LLLine::to_json` if its type is registered.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TokenValue {
+    placeholder: String,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    value: Option<AnyAttribute>,
+}
+
+impl TokenValue {
+    pub fn new<T: 'static + std::fmt::Debug + Clone + Send>(value: T) -> Self {
+        TokenValue {
+            placeholder: String::new(),
+            value: Some(AnyAttribute::new(value)),
+        }
+    }
+
+    /// Stand for the value with `placeholder` in the text of the line
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// The value, if it is a `T`
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.value.as_ref()?.get()
+    }
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LLToken {
     pub(crate) token_idx: usize,
-    // token span position (not token index)
+    /// token span position (not token index), see [LLToken::char_range]
     pub(crate) pos_starts_at: usize,
-    // token span position (not token index)
+    /// token span position (not token index), exclusive
     pub(crate) pos_ends_at: usize,
     pub(crate) token: LToken,
 }
 
+impl LLToken {
+    /// For integrating tokens coming from another tokenizer, see [LLLine::from_tokens].
+    pub fn new(token_idx: usize, pos_starts_at: usize, pos_ends_at: usize, token: LToken) -> Self {
+        LLToken {
+            token_idx,
+            pos_starts_at,
+            pos_ends_at,
+            token,
+        }
+    }
+
+    pub fn token_idx(&self) -> usize {
+        self.token_idx
+    }
+
+    /// Token span position (not token index)
+    pub fn pos_starts_at(&self) -> usize {
+        self.pos_starts_at
+    }
+
+    /// Token span position (not token index)
+    pub fn pos_ends_at(&self) -> usize {
+        self.pos_ends_at
+    }
+
+    /// (start, end) position of this token, the end being exclusive.
+    ///
+    /// Despite the name, these are chars only for a line made with
+    /// [crate::create_line_from_string]: positions are measured with the
+    /// `get_text_size` the line was created with, so a line from
+    /// [crate::create_line_from_input_tokens] with the UTF-16 length, like for
+    /// a JavaScript editor, gives UTF-16 code units. [LLLine::byte_range] is
+    /// always in bytes.
+    pub fn char_range(&self) -> PositionRange {
+        (self.pos_starts_at, self.pos_ends_at)
+    }
+
+    pub fn token(&self) -> &LToken {
+        &self.token
+    }
+}
+
 /// (starts at, ends at) token indexes
 type LRange = (usize, usize);
 /// (starts at, ends at) token positions
 type PositionRange = (usize, usize);
+/// Values of a range, with the metadata they were assigned with
+type WithMetadata<'l, T> = Vec<(&'l T, Option<&'l Metadata>)>;
 
 /// Top-level
 struct LLLineAttrs {
@@ -66,6 +161,13 @@ struct LLLineAttrs {
     /// match_backwards uses [LLSelection::start_idx]
     ends_at: Vec<TypeIdToMany<LRange>>,
     values: HashMap<LRange, type_bucket::TypeBucket>,
+    /// Priority of the values of a type at a range, when it isn't `0`,
+    /// see [LLCursorAssignment::with_priority]
+    priorities: HashMap<(LRange, TypeId), i32>,
+    /// Metadata of each value of a type at a range, in the order of the
+    /// values, when one of them has some. May be shorter than the values,
+    /// the last ones having none.
+    metadata: HashMap<(LRange, TypeId), Vec<Option<Metadata>>>,
 }
 
 pub struct LLLineFind<'l, Found> {
@@ -99,42 +201,66 @@ pub struct LLLine {
     // how much do we actually need of the original Vec if much of the data is put into the bi-map?
     ll_tokens: Vec<LLToken>,
     attrs: LLLineAttrs,
-} 
+}
 
 impl LLLine {
     pub(crate) fn new(ll_tokens: Vec<LLToken>) -> Self {
-        let starts_at: Vec<TypeIdToMany<LRange>> =
-            (0..ll_tokens.len()).map(|_| Default::default()).collect();
-        let ends_at: Vec<TypeIdToMany<LRange>> =
-            (0..ll_tokens.len()).map(|_| Default::default()).collect();
-
-        let mut attrs = LLLineAttrs {
-            ranges: Default::default(),
-            starts_at,
-            ends_at,
-            values: Default::default(),
-        };
+        let mut attrs = LLLineAttrs::with_len(ll_tokens.len());
 
         for (token_idx, ll_token) in ll_tokens.iter().enumerate() {
-            match &ll_token.token {
-                LToken::Text(text, tag) => {
-                    if text.chars().count() == 1 {
-                        // insert char automatically if just one char
-                        attrs.insert((token_idx, token_idx), text.chars().next().unwrap());
-                    }
-                    // insert TextTag automatically
-                    attrs.insert((token_idx, token_idx), tag.clone());
-                }
-                LToken::Value => {
-                    // nothing to do...
-                }
-            }
+            attrs.insert_token(token_idx, &ll_token.token);
         }
 
         LLLine { ll_tokens, attrs }
     }
 
-    pub fn run<R>(mut self, recognizer: &R) -> Self
+    /// Create a line from tokens split by another tokenizer than [crate::create_line_from_input_tokens].
+    ///
+    /// `char` and [TextTag] attributes are assigned the same way.
+    ///
+    /// # Panics
+    ///
+    /// If the `token_idx` of a token is not its index in `ll_tokens`.
+    pub fn from_tokens(ll_tokens: Vec<LLToken>) -> Self {
+        for (idx, ll_token) in ll_tokens.iter().enumerate() {
+            assert_eq!(ll_token.token_idx, idx, "token_idx matches its index");
+        }
+
+        LLLine::new(ll_tokens)
+    }
+
+    pub fn run<R>(self, recognizer: &R) -> Self
+    where
+        R: Resolver,
+    {
+        self.run_with(recognizer, |_, _| {})
+    }
+
+    /// Runs the resolvers of `pipeline` in order, like chaining [LLLine::run]
+    /// calls.
+    pub fn run_all(self, pipeline: &ResolverPipeline) -> Self {
+        pipeline.run(self)
+    }
+
+    /// Like [LLLine::run], calling `observer` with the range and the debug
+    /// string of each value assigned by `recognizer`, before it is stored.
+    ///
+    /// Values dropped for their priority (see
+    /// [LLCursorAssignment::with_priority]) are observed as well.
+    pub fn run_observed<R>(self, recognizer: &R, observer: &mut impl FnMut(LRange, &str)) -> Self
+    where
+        R: Resolver,
+    {
+        self.run_with(recognizer, |range, value| {
+            observer(range, &format!("{:?}", value))
+        })
+    }
+
+    fn run_with<R>(
+        mut self,
+        recognizer: &R,
+        mut on_assignment: impl FnMut(LRange, &R::Attr),
+    ) -> Self
     where
         R: Resolver,
     {
@@ -160,9 +286,22 @@ impl LLLine {
             start_idx,
             end_idx,
             value,
+            priority,
+            metadata,
         } in assignments
         {
-            self.attrs.insert((start_idx, end_idx), value);
+            match value {
+                AssignmentValue::Assign(value) => {
+                    on_assignment((start_idx, end_idx), &value);
+                    self.attrs.insert_with_priority(
+                        (start_idx, end_idx),
+                        value,
+                        priority,
+                        metadata,
+                    );
+                }
+                AssignmentValue::Retract(remove) => remove(&mut self.attrs, (start_idx, end_idx)),
+            }
         }
 
         self
@@ -173,26 +312,230 @@ impl LLLine {
         end_idx: usize,
         attrs: Vec<AnyAttribute>,
     ) {
-        let range = (start_idx, end_idx);
-
         for attr in attrs {
+            self.attrs.insert_any((start_idx, end_idx), attr);
+        }
+    }
+
+    /// Copy the tokens of `range` into a standalone line.
+    ///
+    /// Token indexes and positions are renumbered to start at zero, and only
+    /// the attributes fully contained in `range` are carried over.
+    pub fn slice(&self, range: LRange) -> LLLine {
+        let (start_idx, end_idx) = range;
+        assert!(
+            start_idx <= end_idx && end_idx < self.ll_tokens.len(),
+            "slice range in bounds"
+        );
+
+        let pos_offset = self.pos_start_at(start_idx);
+        let ll_tokens: Vec<LLToken> = self.ll_tokens[start_idx..=end_idx]
+            .iter()
+            .map(|ll_token| LLToken {
+                token_idx: ll_token.token_idx - start_idx,
+                pos_starts_at: ll_token.pos_starts_at - pos_offset,
+                pos_ends_at: ll_token.pos_ends_at - pos_offset,
+                token: ll_token.token.clone(),
+            })
+            .collect();
+
+        let is_contained =
+            |attr_range: &LRange| attr_range.0 >= start_idx && attr_range.1 <= end_idx;
+        let rebase = |attr_range: &LRange| (attr_range.0 - start_idx, attr_range.1 - start_idx);
+
+        let mut attrs = LLLineAttrs::with_len(ll_tokens.len());
+        // going through `ranges` keeps the original insertion order
+        for (type_id, attr_ranges) in self.attrs.ranges.iter() {
+            for attr_range in attr_ranges.iter().filter(|r| is_contained(r)) {
+                let new_range = rebase(attr_range);
+                attrs.starts_at[new_range.0].insert_any_distinct(type_id, new_range);
+                attrs.ends_at[new_range.1].insert_any_distinct(type_id, new_range);
+                attrs.ranges.insert_any_distinct(type_id, new_range);
+            }
+        }
+        for (attr_range, type_bucket) in self.attrs.values.iter() {
+            if is_contained(attr_range) {
+                attrs.values.insert(rebase(attr_range), type_bucket.clone());
+            }
+        }
+        for ((attr_range, type_id), priority) in self.attrs.priorities.iter() {
+            if is_contained(attr_range) {
+                attrs
+                    .priorities
+                    .insert((rebase(attr_range), *type_id), *priority);
+            }
+        }
+        for ((attr_range, type_id), metadata) in self.attrs.metadata.iter() {
+            if is_contained(attr_range) {
+                attrs
+                    .metadata
+                    .insert((rebase(attr_range), *type_id), metadata.clone());
+            }
+        }
+
+        LLLine { ll_tokens, attrs }
+    }
+
+    /// Merge the tokens of `range` into a single token, like the three
+    /// tokens of `New York`, and return the index of the merged token with
+    /// the ranges and types of the attributes dropped by the merge, sorted.
+    ///
+    /// The merged token has the position and the text of the tokens (the
+    /// placeholders of value tokens), and the [TextTag] of the first one, or
+    /// [TextTag::WORD] for a value token. The attributes inside `range`
+    /// collapse onto the merged token, following the priorities as if they
+    /// were assigned there, those after it shift back, and those spanning
+    /// over it shrink. The attributes partially overlapping `range` are
+    /// dropped. The [TextTag] and [char]
+    /// attributes of the merged tokens are replaced like in a new line.
+    ///
+    /// # Panics
+    ///
+    /// If `range` isn't in the line.
+    pub fn merge_range(&mut self, range: LRange) -> (usize, Vec<(LRange, TypeId)>) {
+        let (start_idx, end_idx) = range;
+        assert!(
+            start_idx <= end_idx && end_idx < self.ll_tokens.len(),
+            "merge range in bounds"
+        );
+        let shift = end_idx - start_idx;
+
+        let merged_tokens = &self.ll_tokens[start_idx..=end_idx];
+        let text: String = merged_tokens
+            .iter()
+            .map(|ll_token| match &ll_token.token {
+                LToken::Text(text, _) => text.as_str(),
+                LToken::Value(token_value) => token_value.placeholder(),
+            })
+            .collect();
+        let tag = match &merged_tokens[0].token {
+            LToken::Text(_, tag) => tag.clone(),
+            LToken::Value(_) => TextTag::WORD,
+        };
+        let merged_token = LLToken {
+            token_idx: start_idx,
+            pos_starts_at: merged_tokens[0].pos_starts_at,
+            pos_ends_at: merged_tokens[shift].pos_ends_at,
+            token: LToken::Text(text, tag),
+        };
+        self.ll_tokens
+            .splice(start_idx..=end_idx, std::iter::once(merged_token));
+        for ll_token in &mut self.ll_tokens[start_idx + 1..] {
+            ll_token.token_idx -= shift;
+        }
+
+        let mut old_attrs =
+            std::mem::replace(&mut self.attrs, LLLineAttrs::with_len(self.ll_tokens.len()));
+        self.attrs
+            .insert_token(start_idx, &self.ll_tokens[start_idx].token);
+
+        let auto_type_ids = [TypeId::of::<char>(), TypeId::of::<TextTag>()];
+        // going through `ranges` keeps the original insertion order
+        let entries: Vec<(LRange, TypeId)> = old_attrs
+            .ranges
+            .iter()
+            .flat_map(|(type_id, ranges)| ranges.iter().map(move |range| (*range, type_id)))
+            .collect();
+        let mut dropped = Vec::new();
+        for (attr_range, type_id) in entries {
+            let (attr_start, attr_end) = attr_range;
+            let is_inside = attr_start >= start_idx && attr_end <= end_idx;
+            if is_inside && auto_type_ids.contains(&type_id) {
+                continue;
+            }
+            let straddles = (start_idx < attr_start && attr_start <= end_idx && attr_end > end_idx)
+                || (attr_start < start_idx && start_idx <= attr_end && attr_end < end_idx);
+            if straddles {
+                dropped.push((attr_range, type_id));
+                continue;
+            }
+
+            let new_idx = |token_idx: usize| {
+                if token_idx < start_idx {
+                    token_idx
+                } else if token_idx > end_idx {
+                    token_idx - shift
+                } else {
+                    start_idx
+                }
+            };
+            let new_range = (new_idx(attr_start), new_idx(attr_end));
             self.attrs
-                .starts_at
-                .get_mut(start_idx)
-                .expect("has initial starts_at value in bounds")
-                .insert_any_distinct(attr.type_id(), range);
-            self.attrs
-                .ends_at
-                .get_mut(end_idx)
-                .expect("has initial ends_at value in bounds")
-                .insert_any_distinct(attr.type_id(), range);
-            self.attrs.ranges.insert_any_distinct(attr.type_id(), range);
-            self.attrs
-                .values
-                .entry(range)
-                .or_default()
-                .insert_any_attribute(attr);
+                .move_any_from(&mut old_attrs, attr_range, type_id, new_range);
+        }
+
+        // the emptied buckets are kept, matchers expect one for every token
+        for token_idx in 0..self.ll_tokens.len() {
+            self.attrs.values.entry((token_idx, token_idx)).or_default();
+        }
+
+        dropped.sort_unstable();
+        (start_idx, dropped)
+    }
+
+    /// The raw attribute indexes, to debug resolvers and indexing: for each
+    /// token index the ranges starting and ending there, then the ranges of
+    /// each type.
+    ///
+    /// Entries are sorted, the indexes themselves having no order.
+    pub fn debug_dump(&self) -> String {
+        let entries = |index: &TypeIdToMany<LRange>| {
+            let mut entries: Vec<(LRange, &str)> = index
+                .iter()
+                .flat_map(|(type_id, ranges)| {
+                    ranges
+                        .iter()
+                        .map(move |range| (*range, self.type_name(range, type_id)))
+                })
+                .collect();
+            entries.sort_unstable();
+            entries
+                .iter()
+                .map(|(range, name)| format!("{:?} {}", range, name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        let mut dump = String::new();
+        for (title, index) in [
+            ("starts_at", &self.attrs.starts_at),
+            ("ends_at", &self.attrs.ends_at),
+        ] {
+            writeln!(dump, "{}:", title).unwrap();
+            for (token_idx, token_index) in index.iter().enumerate() {
+                writeln!(dump, "  {}: {}", token_idx, entries(token_index)).unwrap();
+            }
         }
+
+        writeln!(dump, "ranges:").unwrap();
+        let mut ranges: Vec<(&str, Vec<LRange>)> = self
+            .attrs
+            .ranges
+            .iter()
+            .map(|(type_id, ranges)| {
+                let name = ranges
+                    .first()
+                    .map_or("<unknown>", |range| self.type_name(range, type_id));
+                let mut ranges = ranges.to_vec();
+                ranges.sort_unstable();
+                (name, ranges)
+            })
+            .collect();
+        ranges.sort_unstable();
+        for (name, ranges) in ranges {
+            writeln!(dump, "  {}: {:?}", name, ranges).unwrap();
+        }
+
+        dump
+    }
+
+    /// The name of the `type_id` attributes at `range`
+    fn type_name(&self, range: &LRange, type_id: TypeId) -> &'static str {
+        self.attrs
+            .values
+            .get(range)
+            .and_then(|bucket| bucket.type_name(type_id))
+            .unwrap_or("<unknown>")
     }
 
     /// Get a reference to the ll line's ll tokens.
@@ -208,7 +551,7 @@ impl LLLine {
                 let forwards = XForwards { from_idx: i };
 
                 matcher
-                    .go(&forwards, &self)
+                    .go(&forwards, self)
                     .into_iter()
                     .map(move |(out, next_idx)| LLLineFind {
                         start_pos_at: self.pos_start_at(i),
@@ -220,6 +563,33 @@ impl LLLine {
             .collect()
     }
 
+    /// Like [LLLine::find], matching backwards from each token: `matcher`
+    /// starts at the end of what it finds and extends towards the start of
+    /// the line, so [x::seq] matches its last piece first.
+    ///
+    /// Results are sorted by their (start, end) range, single-token matches
+    /// coming out the same as with [LLLine::find].
+    pub fn find_backwards<'l, M: XMatch<'l>>(&'l self, matcher: &M) -> Vec<LLLineFind<'l, M::Out>> {
+        let mut found: Vec<LLLineFind<'l, M::Out>> = (0..self.ll_tokens.len())
+            .flat_map(|i| {
+                let backwards = XBackwards { from_idx: i };
+
+                matcher
+                    .go(&backwards, self)
+                    .into_iter()
+                    .map(move |(out, start_idx)| LLLineFind {
+                        start_pos_at: self.pos_start_at(start_idx.0),
+                        end_pos_at: self.pos_end_at(i),
+                        found: out,
+                        _phantom: std::marker::PhantomData,
+                    })
+            })
+            .collect();
+        found.sort_by_key(LLLineFind::range);
+
+        found
+    }
+
     fn pos_end_at(&self, idx: usize) -> usize {
         self.ll_tokens
             .get(idx)
@@ -234,32 +604,493 @@ impl LLLine {
     }
 
     /// Returns Attributes' information outside `LLLine`
-    pub fn query<'a, T: 'static>(&'a self) -> Vec<(LRange, String, Vec<&T>)> {
+    pub fn query<T: 'static>(&self) -> Vec<(LRange, String, Vec<&T>)> {
         self.attrs
             .ranges
             .get::<T>()
             .iter()
             .map(|range| {
-                let text =
-                    String::from_iter(self.ll_tokens[range.0..=range.1].iter().map(|token| {
-                        match &token.token {
-                            LToken::Text(text, _) => text,
-                            LToken::Value => "",
-                        }
-                    }));
-
                 (
                     *range,
-                    text,
+                    self.text(*range),
                     self.attrs.values[range].get::<T>().iter().collect(),
                 )
             })
             .collect()
     }
+
+    /// The text of the tokens of `range`, with the placeholders of value tokens
+    fn text(&self, range: LRange) -> String {
+        String::from_iter(self.ll_tokens[range.0..=range.1].iter().map(
+            |token| match &token.token {
+                LToken::Text(text, _) => text,
+                LToken::Value(token_value) => token_value.placeholder(),
+            },
+        ))
+    }
+
+    /// The `T` values whose range overlaps the token range `range`, sorted by
+    /// range.
+    ///
+    /// Both ends of ranges are inclusive, so a value only sharing one token
+    /// with `range`, like `(2, 3)` for `(3, 5)`, overlaps it, while `(1, 2)`
+    /// doesn't.
+    pub fn query_overlapping<T: 'static>(&self, range: LRange) -> Vec<(LRange, &T)> {
+        self.query_ranges(|(start_idx, end_idx)| start_idx <= range.1 && end_idx >= range.0)
+    }
+
+    /// The `T` values whose range is within the token range `range`, ends
+    /// included, sorted by range. `range` itself is within `range`.
+    pub fn query_contained_in<T: 'static>(&self, range: LRange) -> Vec<(LRange, &T)> {
+        self.query_ranges(|(start_idx, end_idx)| start_idx >= range.0 && end_idx <= range.1)
+    }
+
+    fn query_ranges<T: 'static>(
+        &self,
+        mut filter: impl FnMut(LRange) -> bool,
+    ) -> Vec<(LRange, &T)> {
+        let mut ranges: Vec<LRange> = self
+            .attrs
+            .ranges
+            .get::<T>()
+            .iter()
+            .copied()
+            .filter(|range| filter(*range))
+            .collect();
+        ranges.sort_unstable();
+
+        ranges
+            .into_iter()
+            .flat_map(|range| {
+                self.attrs.values[&range]
+                    .get::<T>()
+                    .iter()
+                    .map(move |value| (range, value))
+            })
+            .collect()
+    }
+
+    /// Like [LLLine::query], with the [Metadata] of each value, if it was
+    /// assigned with some
+    pub fn query_with_metadata<T: 'static>(&self) -> Vec<(LRange, String, WithMetadata<'_, T>)> {
+        self.attrs
+            .ranges
+            .get::<T>()
+            .iter()
+            .map(|range| {
+                (
+                    *range,
+                    self.text(*range),
+                    self.attrs.get_with_metadata::<T>(*range),
+                )
+            })
+            .collect()
+    }
+
+    /// The index of the token at position `pos`, in the sizes given when
+    /// creating the line. A position on the boundary of two tokens is in the
+    /// token starting there.
+    fn token_idx_at(&self, pos: usize) -> Option<usize> {
+        let token_idx = self
+            .ll_tokens
+            .partition_point(|token| token.pos_ends_at <= pos);
+        self.ll_tokens
+            .get(token_idx)
+            .filter(|token| token.pos_starts_at <= pos)
+            .map(|_| token_idx)
+    }
+
+    /// The ranges of attributes of any type covering the token at position
+    /// `pos` (see [LLLine::query_type_at]), with the type name of their
+    /// attributes. Sorted by range, then type name.
+    ///
+    /// This includes the `char` and [TextTag] of the token itself, so only a
+    /// position past the end of the line finds nothing.
+    pub fn query_at(&self, pos: usize) -> Vec<(LRange, &'static str)> {
+        let token_idx = match self.token_idx_at(pos) {
+            Some(token_idx) => token_idx,
+            None => return Vec::new(),
+        };
+
+        let mut found: Vec<(LRange, &'static str)> = self.attrs.starts_at[..=token_idx]
+            .iter()
+            .flat_map(|starting| starting.iter())
+            .flat_map(|(type_id, ranges)| {
+                ranges
+                    .iter()
+                    .filter(|range| range.1 >= token_idx)
+                    .map(move |range| (*range, self.type_name(range, type_id)))
+            })
+            .collect();
+        found.sort_unstable();
+
+        found
+    }
+
+    /// The `T` attributes covering the token at position `pos`, in the sizes
+    /// given when creating the line. A position on the boundary of two
+    /// tokens is in the token starting there. Sorted by range.
+    pub fn query_type_at<T: 'static>(&self, pos: usize) -> Vec<(LRange, Vec<&T>)> {
+        let token_idx = match self.token_idx_at(pos) {
+            Some(token_idx) => token_idx,
+            None => return Vec::new(),
+        };
+
+        let mut found: Vec<(LRange, Vec<&T>)> = self
+            .attrs
+            .ranges
+            .get::<T>()
+            .iter()
+            .filter(|range| range.0 <= token_idx && token_idx <= range.1)
+            .map(|range| (*range, self.attrs.values[range].get::<T>().iter().collect()))
+            .collect();
+        found.sort_unstable_by_key(|(range, _)| *range);
+
+        found
+    }
+
+    /// A selection per [crate::Sentence] of the line, in order, once a
+    /// [crate::SentenceResolver] ran.
+    ///
+    /// Yields nothing when the line has no sentence layer, rather than the
+    /// whole line, so a missing [crate::SentenceResolver] doesn't go unnoticed.
+    ///
+    /// The line is taken behind an [Rc] since selections share it, like in [LLSelection::from_line].
+    pub fn sentences(self: &Rc<Self>) -> impl Iterator<Item = LLSelection> {
+        let ll_line = self.clone();
+        let mut ranges = self.attrs.ranges.get::<crate::Sentence>().to_vec();
+        ranges.sort_unstable();
+
+        ranges
+            .into_iter()
+            .map(move |(start_idx, end_idx)| LLSelection {
+                ll_line: ll_line.clone(),
+                start_idx,
+                end_idx,
+            })
+    }
+
+    /// Remove the `char` and [TextTag] attributes assigned to each token on creation.
+    ///
+    /// Only the value matching the token is removed, so a [TextTag] assigned
+    /// by a resolver on a token with another tag is kept.
+    ///
+    /// Matchers relying on them stop matching: [x::token_has_any] with `char`
+    /// values, [x::whitespace], and [x::attr] or [x::attr_eq] with [TextTag],
+    /// along with the resolvers using them, like the bundled ones.
+    /// [x::token_text] reads tokens directly and keeps working.
+    pub fn remove_auto_attrs(&mut self) {
+        for (token_idx, ll_token) in self.ll_tokens.iter().enumerate() {
+            if let LToken::Text(text, tag) = &ll_token.token {
+                let mut chars = text.chars();
+                if let (Some(ch), None) = (chars.next(), chars.next()) {
+                    self.attrs.remove_value((token_idx, token_idx), &ch);
+                }
+                self.attrs.remove_value((token_idx, token_idx), tag);
+            }
+        }
+    }
+
+    /// (start, end) byte offsets of the tokens of `range` in the line text,
+    /// the end being exclusive, to slice the text the line was created from.
+    ///
+    /// Unlike the positions of [LLToken::char_range], bytes don't depend on
+    /// the `get_text_size` of the line. Value tokens count the bytes of
+    /// their placeholder.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is out of the line.
+    pub fn byte_range(&self, range: LRange) -> (usize, usize) {
+        assert!(
+            range.0 <= range.1 && range.1 < self.ll_tokens.len(),
+            "token range in bounds"
+        );
+        let token_bytes = self.token_bytes();
+        (token_bytes[range.0].0, token_bytes[range.1].1)
+    }
+
+    /// Assign `value` to the tokens exactly covering the bytes from
+    /// `byte_start` (inclusive) to `byte_end` (exclusive) of the line text.
+    ///
+    /// Bytes are counted in the text of the tokens, whatever the positions of
+    /// the line are measured in. Returns the token range `value` was assigned to.
+    pub fn attr_from_byte_range<T: 'static + std::fmt::Debug + Clone + Send>(
+        &mut self,
+        byte_start: usize,
+        byte_end: usize,
+        value: T,
+    ) -> Result<LRange, ByteRangeError> {
+        let token_bytes = self.token_bytes();
+        let byte_len = token_bytes.last().map_or(0, |(_, end)| *end);
+
+        if byte_start >= byte_end || byte_end > byte_len {
+            return Err(ByteRangeError::InvalidRange);
+        }
+
+        let start_idx = token_bytes
+            .iter()
+            .position(|(start, _)| *start == byte_start);
+        let end_idx = token_bytes.iter().rposition(|(_, end)| *end == byte_end);
+        match (start_idx, end_idx) {
+            (Some(start_idx), Some(end_idx)) if start_idx <= end_idx => {
+                self.attrs.insert((start_idx, end_idx), value);
+                Ok((start_idx, end_idx))
+            }
+            _ => {
+                let covering_start_idx = token_bytes
+                    .iter()
+                    .position(|(start, end)| *start <= byte_start && byte_start < *end)
+                    .expect("byte_start is in bounds");
+                let covering_end_idx = token_bytes
+                    .iter()
+                    .rposition(|(start, end)| *start < byte_end && byte_end <= *end)
+                    .expect("byte_end is in bounds");
+
+                Err(ByteRangeError::Misaligned {
+                    covering_range: (covering_start_idx, covering_end_idx),
+                    covering_bytes: (
+                        token_bytes[covering_start_idx].0,
+                        token_bytes[covering_end_idx].1,
+                    ),
+                })
+            }
+        }
+    }
+
+    /// Every run of `n` [TextTag::WORD] tokens only separated by whitespace,
+    /// with their text joined by single spaces.
+    ///
+    /// Other tokens, like punctuation, break the runs: `a b, c` has the
+    /// bigram `a b` but not `b c`.
+    pub fn word_ngrams(&self, n: usize) -> Vec<(LRange, String)> {
+        if n == 0 {
+            return Vec::new();
+        }
+
+        // words only separated by whitespace
+        let mut runs: Vec<Vec<(usize, &str)>> = vec![Vec::new()];
+        for (token_idx, ll_token) in self.ll_tokens.iter().enumerate() {
+            match &ll_token.token {
+                LToken::Text(text, TextTag::WORD) => runs
+                    .last_mut()
+                    .expect("starts with one run")
+                    .push((token_idx, text)),
+                LToken::Text(_, TextTag::SPACE) => {}
+                _ => runs.push(Vec::new()),
+            }
+        }
+
+        runs.iter()
+            .flat_map(|run| run.windows(n))
+            .map(|window| {
+                let words: Vec<&str> = window.iter().map(|(_, text)| *text).collect();
+                ((window[0].0, window[n - 1].0), words.join(" "))
+            })
+            .collect()
+    }
+
+    /// Indexes of the tokens without any attribute but their `char` and [TextTag].
+    ///
+    /// `char` and [TextTag] attributes are ignored even when a resolver
+    /// assigned them.
+    pub fn unannotated_tokens(&self) -> Vec<usize> {
+        let auto_type_ids = [TypeId::of::<char>(), TypeId::of::<TextTag>()];
+        let mut is_annotated = vec![false; self.ll_tokens.len()];
+
+        for (type_id, ranges) in self.attrs.ranges.iter() {
+            if auto_type_ids.contains(&type_id) {
+                continue;
+            }
+            for (start_idx, end_idx) in ranges {
+                is_annotated[*start_idx..=*end_idx].fill(true);
+            }
+        }
+
+        (0..self.ll_tokens.len())
+            .filter(|token_idx| !is_annotated[*token_idx])
+            .collect()
+    }
+
+    /// Remove the attributes assigned to tokens touched by an edit of the
+    /// bytes from `byte_start` (inclusive) to `byte_end` (exclusive) of the
+    /// line text, and return their ranges and types, sorted.
+    ///
+    /// An insertion, with `byte_start == byte_end`, touches the tokens on
+    /// both sides of it. Bytes are counted as in [LLLine::attr_from_byte_range].
+    /// The [TextTag] and [char] attributes describe the tokens themselves, and
+    /// are left for re-tokenization to replace.
+    ///
+    /// Returns [ByteRangeError::InvalidRange], removing nothing, if the bytes
+    /// aren't in the line text, like for an edit of an outdated text.
+    pub fn invalidate_byte_range(
+        &mut self,
+        byte_start: usize,
+        byte_end: usize,
+    ) -> Result<Vec<(LRange, TypeId)>, ByteRangeError> {
+        let token_bytes = self.token_bytes();
+        let byte_len = token_bytes.last().map_or(0, |(_, end)| *end);
+
+        if byte_start > byte_end || byte_end > byte_len {
+            return Err(ByteRangeError::InvalidRange);
+        }
+
+        let is_touched = |token_idx: usize| {
+            let (start, end) = token_bytes[token_idx];
+            if byte_start == byte_end {
+                start <= byte_start && byte_start <= end
+            } else {
+                start < byte_end && byte_start < end
+            }
+        };
+        let auto_type_ids = [TypeId::of::<char>(), TypeId::of::<TextTag>()];
+
+        let mut invalidated: Vec<(LRange, TypeId)> = self
+            .attrs
+            .ranges
+            .iter()
+            .filter(|(type_id, _)| !auto_type_ids.contains(type_id))
+            .flat_map(|(type_id, ranges)| {
+                ranges
+                    .iter()
+                    .filter(|(start_idx, end_idx)| (*start_idx..=*end_idx).any(is_touched))
+                    .map(move |range| (*range, type_id))
+            })
+            .collect();
+        invalidated.sort_unstable();
+
+        for (range, type_id) in &invalidated {
+            self.attrs.remove_any(*range, *type_id);
+        }
+
+        Ok(invalidated)
+    }
+
+    /// (start, end) bytes of each token in the line text, `end` being exclusive
+    fn token_bytes(&self) -> Vec<(usize, usize)> {
+        let mut token_bytes = Vec::with_capacity(self.ll_tokens.len());
+        let mut byte_offset = 0;
+        for ll_token in &self.ll_tokens {
+            let len = match &ll_token.token {
+                LToken::Text(text, _) => text.len(),
+                LToken::Value(token_value) => token_value.placeholder().len(),
+            };
+            token_bytes.push((byte_offset, byte_offset + len));
+            byte_offset += len;
+        }
+
+        token_bytes
+    }
+
+    /// Distinct `T` values and the range they are assigned to
+    fn debug_values<T: 'static + std::fmt::Debug>(&self) -> BTreeSet<(LRange, String)> {
+        self.attrs
+            .ranges
+            .get::<T>()
+            .iter()
+            .flat_map(|range| {
+                self.attrs.values[range]
+                    .get_debug::<T>()
+                    .into_iter()
+                    .map(move |value| (*range, value))
+            })
+            .collect()
+    }
+
+    /// The `T` attribute spanning the most tokens.
+    ///
+    /// Among equally long ranges, the first one assigned wins, and among
+    /// several `T` values on that range, the first one.
+    pub fn longest_attr<T: 'static>(&self) -> Option<(LRange, &T)> {
+        self.attrs
+            .ranges
+            .get::<T>()
+            .iter()
+            .min_by_key(|range| std::cmp::Reverse(range.1 - range.0))
+            .and_then(|range| Some((*range, self.attrs.values[range].get::<T>().first()?)))
+    }
 }
 
 impl LLLineAttrs {
-    fn insert<T: 'static + std::fmt::Debug>(&mut self, range: LRange, value: T) {
+    fn with_len(len: usize) -> Self {
+        LLLineAttrs {
+            ranges: Default::default(),
+            starts_at: (0..len).map(|_| Default::default()).collect(),
+            ends_at: (0..len).map(|_| Default::default()).collect(),
+            values: Default::default(),
+            priorities: Default::default(),
+            metadata: Default::default(),
+        }
+    }
+
+    fn insert<T: 'static + std::fmt::Debug + Clone + Send>(&mut self, range: LRange, value: T) {
+        self.insert_with_priority(range, value, 0, None)
+    }
+
+    /// Insert the attributes describing the token `token` itself
+    fn insert_token(&mut self, token_idx: usize, token: &LToken) {
+        match token {
+            LToken::Text(text, tag) => {
+                let mut chars = text.chars();
+                if let (Some(ch), None) = (chars.next(), chars.next()) {
+                    // insert char automatically if just one char
+                    self.insert((token_idx, token_idx), ch);
+                }
+                // insert TextTag automatically
+                self.insert((token_idx, token_idx), tag.clone());
+            }
+            LToken::Value(token_value) => {
+                // insert the value automatically, like TextTag
+                if let Some(attr) = &token_value.value {
+                    self.insert_any((token_idx, token_idx), attr.clone());
+                }
+                // matchers expect a bucket for every token, even without a value
+                self.values.entry((token_idx, token_idx)).or_default();
+            }
+        }
+    }
+
+    fn insert_any(&mut self, range: LRange, attr: AnyAttribute) {
+        self.starts_at
+            .get_mut(range.0)
+            .expect("has initial starts_at value in bounds")
+            .insert_any_distinct(attr.type_id(), range);
+        self.ends_at
+            .get_mut(range.1)
+            .expect("has initial ends_at value in bounds")
+            .insert_any_distinct(attr.type_id(), range);
+        self.ranges.insert_any_distinct(attr.type_id(), range);
+        self.values
+            .entry(range)
+            .or_default()
+            .insert_any_attribute(attr);
+    }
+
+    /// Insert `value` unless `range` has `T` values of a higher priority,
+    /// replacing the `T` values of a lower priority
+    fn insert_with_priority<T: 'static + std::fmt::Debug + Clone + Send>(
+        &mut self,
+        range: LRange,
+        value: T,
+        priority: i32,
+        metadata: Option<Metadata>,
+    ) {
+        let key = (range, TypeId::of::<T>());
+        if let Some(bucket) = self.values.get_mut(&range).filter(|b| b.contains::<T>()) {
+            let current = self.priorities.get(&key).copied().unwrap_or(0);
+            if priority < current {
+                return;
+            } else if priority > current {
+                bucket.take::<T>();
+                self.metadata.remove(&key);
+            }
+        }
+        if priority == 0 {
+            self.priorities.remove(&key);
+        } else {
+            self.priorities.insert(key, priority);
+        }
+
         self.starts_at
             .get_mut(range.0)
             .expect("has initial starts_at value in bounds")
@@ -269,10 +1100,174 @@ impl LLLineAttrs {
             .expect("has initial ends_at value in bounds")
             .insert_distinct::<T>(range);
         self.ranges.insert_distinct::<T>(range);
-        self.values.entry(range).or_default().insert(value);
+        let bucket = self.values.entry(range).or_default();
+        bucket.insert(value);
+
+        if metadata.is_some() || self.metadata.contains_key(&key) {
+            let value_idx = bucket.get::<T>().len() - 1;
+            let value_metadata = self.metadata.entry(key).or_default();
+            value_metadata.resize(value_idx, None);
+            value_metadata.push(metadata);
+        }
+    }
+
+    /// Move the values of the type `type_id` at `range` of `from` onto
+    /// `new_range`, following their priority like
+    /// [LLLineAttrs::insert_with_priority]
+    fn move_any_from(
+        &mut self,
+        from: &mut LLLineAttrs,
+        range: LRange,
+        type_id: TypeId,
+        new_range: LRange,
+    ) {
+        let from_key = (range, type_id);
+        let priority = from.priorities.get(&from_key).copied().unwrap_or(0);
+        let from_metadata = from.metadata.remove(&from_key);
+        let from_bucket = match from.values.get_mut(&range) {
+            Some(bucket) if bucket.contains_any(type_id) => bucket,
+            _ => return,
+        };
+
+        let key = (new_range, type_id);
+        if let Some(bucket) = self
+            .values
+            .get_mut(&new_range)
+            .filter(|b| b.contains_any(type_id))
+        {
+            let current = self.priorities.get(&key).copied().unwrap_or(0);
+            if priority < current {
+                return;
+            } else if priority > current {
+                bucket.remove_any(type_id);
+                self.metadata.remove(&key);
+            }
+        }
+        if priority == 0 {
+            self.priorities.remove(&key);
+        } else {
+            self.priorities.insert(key, priority);
+        }
+
+        self.starts_at[new_range.0].insert_any_distinct(type_id, new_range);
+        self.ends_at[new_range.1].insert_any_distinct(type_id, new_range);
+        self.ranges.insert_any_distinct(type_id, new_range);
+        let bucket = self.values.entry(new_range).or_default();
+        let value_idx = bucket.count_any(type_id);
+        from_bucket.move_any(type_id, bucket);
+
+        if from_metadata.is_some() || self.metadata.contains_key(&key) {
+            let value_metadata = self.metadata.entry(key).or_default();
+            value_metadata.resize(value_idx, None);
+            value_metadata.extend(from_metadata.unwrap_or_default());
+        }
+    }
+
+    /// Remove one `T` value equal to `value` from `range`, and `range` from
+    /// the indexes of `T` once it has no `T` value left.
+    fn remove_value<T: 'static + PartialEq>(&mut self, range: LRange, value: &T) {
+        let bucket = match self.values.get_mut(&range) {
+            Some(bucket) => bucket,
+            None => return,
+        };
+        let key = (range, TypeId::of::<T>());
+        let value_idx = bucket.get::<T>().iter().position(|v| v == value);
+        // the emptied bucket is kept, matchers expect one for every token
+        if !bucket.remove_first(value) {
+            return;
+        }
+        if let (Some(value_idx), Some(value_metadata)) = (value_idx, self.metadata.get_mut(&key)) {
+            if value_idx < value_metadata.len() {
+                value_metadata.remove(value_idx);
+            }
+        }
+        if bucket.contains::<T>() {
+            return;
+        }
+
+        self.starts_at[range.0].remove::<T>(&range);
+        self.ends_at[range.1].remove::<T>(&range);
+        self.ranges.remove::<T>(&range);
+        self.priorities.remove(&key);
+        self.metadata.remove(&key);
+    }
+
+    /// Remove all the `T` values from `range`, keeping the values of the
+    /// other types at `range`.
+    fn remove<T: 'static>(&mut self, range: LRange) {
+        if range.0 <= range.1 && range.1 < self.starts_at.len() {
+            self.remove_any(range, TypeId::of::<T>());
+        }
+    }
+
+    /// Remove all the values of the type of `type_id` from `range`, and
+    /// `range` from the indexes of the type.
+    fn remove_any(&mut self, range: LRange, type_id: TypeId) {
+        // the emptied bucket is kept, matchers expect one for every token
+        if let Some(bucket) = self.values.get_mut(&range) {
+            bucket.remove_any(type_id);
+        }
+
+        self.starts_at[range.0].remove_any(type_id, &range);
+        self.ends_at[range.1].remove_any(type_id, &range);
+        self.ranges.remove_any(type_id, &range);
+        self.priorities.remove(&(range, type_id));
+        self.metadata.remove(&(range, type_id));
+    }
+
+    /// The `T` values of `range`, with their metadata
+    fn get_with_metadata<T: 'static>(&self, range: LRange) -> WithMetadata<'_, T> {
+        let value_metadata = self
+            .metadata
+            .get(&(range, TypeId::of::<T>()))
+            .map_or(&[][..], Vec::as_slice);
+
+        self.values
+            .get(&range)
+            .map_or(&[][..], |bucket| bucket.get::<T>())
+            .iter()
+            .enumerate()
+            .map(|(value_idx, value)| {
+                (
+                    value,
+                    value_metadata.get(value_idx).and_then(Option::as_ref),
+                )
+            })
+            .collect()
+    }
+}
+
+/// Why [LLLine::attr_from_byte_range] assigned nothing, or
+/// [LLLine::invalidate_byte_range] removed nothing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ByteRangeError {
+    /// The range is reversed, empty (except for an insertion to invalidate)
+    /// or goes past the end of the line
+    InvalidRange,
+    /// The range starts or ends inside a token
+    Misaligned {
+        /// (start, end) token indexes of the tokens the range touches
+        covering_range: (usize, usize),
+        /// (start, end) bytes of these tokens, `end` being exclusive
+        covering_bytes: (usize, usize),
+    },
+}
+
+impl fmt::Display for ByteRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ByteRangeError::InvalidRange => f.write_str("byte range is empty or out of bounds"),
+            ByteRangeError::Misaligned { covering_bytes, .. } => write!(
+                f,
+                "byte range is not aligned to token boundaries, the closest aligned range is {}..{}",
+                covering_bytes.0, covering_bytes.1
+            ),
+        }
     }
 }
 
+impl std::error::Error for ByteRangeError {}
+
 #[track_caller]
 fn assert_ll_lines_equals(first: &Rc<LLLine>, second: &Rc<LLLine>) {
     if !Rc::ptr_eq(first, second) {
@@ -280,6 +1275,27 @@ fn assert_ll_lines_equals(first: &Rc<LLLine>, second: &Rc<LLLine>) {
     }
 }
 
+/// Who assigned a value and how sure it was, for a later resolver to pick
+/// between competing values, see [LLCursorAssignment::with_confidence].
+///
+/// Metadata is kept by [LLLine::slice], but not saved in JSON.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Metadata {
+    /// `1.0` unless set
+    pub confidence: f32,
+    /// Which resolver assigned the value, empty unless set
+    pub source: &'static str,
+}
+
+impl Default for Metadata {
+    fn default() -> Self {
+        Metadata {
+            confidence: 1.0,
+            source: "",
+        }
+    }
+}
+
 // TODO rename
 #[derive(Debug)]
 pub struct LLCursorAssignment<Attr> {
@@ -287,15 +1303,147 @@ pub struct LLCursorAssignment<Attr> {
     start_idx: usize,
     end_idx: usize,
     // provided from resolver
-    value: Attr,
+    value: AssignmentValue<Attr>,
+    priority: i32,
+    metadata: Option<Metadata>,
+}
+
+#[derive(Debug)]
+enum AssignmentValue<Attr> {
+    Assign(Attr),
+    /// Removes the values of a type, with [LLLineAttrs::remove]
+    Retract(fn(&mut LLLineAttrs, LRange)),
+}
+
+impl<Attr> LLCursorAssignment<Attr> {
+    /// Removes all the `T` values assigned to `range` by earlier resolvers,
+    /// leaving the values of other types at `range` alone.
+    ///
+    /// Assignments are applied in order, so a resolver can retract a range
+    /// and assign it again, as in reclassifying a `Number` as a `Year`.
+    pub fn retract<T: 'static>(range: (usize, usize)) -> Self {
+        LLCursorAssignment {
+            start_idx: range.0,
+            end_idx: range.1,
+            value: AssignmentValue::Retract(LLLineAttrs::remove::<T>),
+            priority: 0,
+            metadata: None,
+        }
+    }
+
+    /// Which value to keep when resolvers assign values of the same type to
+    /// the same range, `0` by default.
+    ///
+    /// On insertion, the values of a lower priority already at the range are
+    /// replaced, and the new value is dropped if the range has values of a
+    /// higher priority. Values of equal priority are all kept, in the order
+    /// they were assigned, so [LLLine::query] and the matchers return the
+    /// earliest one first.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Store the value with [Metadata] of `confidence`, see
+    /// [LLLine::query_with_metadata] and [LLSelection::attrs_with_metadata].
+    ///
+    /// Unlike [LLCursorAssignment::with_priority], values of a range are all
+    /// kept whatever their confidence, for a later resolver to pick from.
+    pub fn with_confidence(mut self, confidence: f32) -> Self {
+        self.metadata
+            .get_or_insert_with(Metadata::default)
+            .confidence = confidence;
+        self
+    }
+
+    /// Store the value with [Metadata] naming its `source`, like the name
+    /// of the resolver.
+    pub fn with_source(mut self, source: &'static str) -> Self {
+        self.metadata.get_or_insert_with(Metadata::default).source = source;
+        self
+    }
 }
 
 pub trait Resolver {
     /// The kind of value that this resolver will assign into the LLLine.
     ///
     /// It is constrained to [std::fmt::Debug] in order to ensure that it's easy
-    /// to debug with [layered_nlp::LLLineDisplay].
-    type Attr: std::fmt::Debug + 'static;
+    /// to debug with [layered_nlp::LLLineDisplay], to [Clone] so that
+    /// lines can be sliced with [LLLine::slice], and to [Send] so that lines
+    /// can be moved across threads.
+    type Attr: std::fmt::Debug + Clone + Send + 'static;
     /// How to perform the assignments.
     fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>>;
 }
+
+/// Runs `resolver` on `ll_line`, then checks that running it once more
+/// assigns no new attributes, as happens with resolvers re-matching their own
+/// output.
+///
+/// Returns the line after the first run. Only checks in debug builds, in
+/// release builds this is the same as [LLLine::run].
+///
+/// # Panics
+///
+/// If the second run assigns a value which the first run didn't assign on the same range.
+#[track_caller]
+pub fn assert_resolver_idempotent<R: Resolver>(ll_line: LLLine, resolver: &R) -> LLLine {
+    let ll_line = ll_line.run(resolver);
+
+    if cfg!(debug_assertions) && !ll_line.ll_tokens.is_empty() {
+        let first_run = ll_line.debug_values::<R::Attr>();
+        let second_run = ll_line
+            .slice((0, ll_line.ll_tokens.len() - 1))
+            .run(resolver)
+            .debug_values::<R::Attr>();
+        let new_values: Vec<_> = second_run.difference(&first_run).collect();
+
+        assert!(
+            new_values.is_empty(),
+            "resolver is not idempotent, the second run assigned {:?}",
+            new_values
+        );
+    }
+
+    ll_line
+}
+
+/// [Resolver] running a closure, create with [resolver_fn].
+pub struct FnResolver<F, A> {
+    f: F,
+    _attr: PhantomData<fn() -> A>,
+}
+
+/// Use a closure as a [Resolver], handy for one-off recognizers.
+///
+/// ```
+/// use layered_nlp::{create_line_from_string, resolver_fn, x, FinishWith, LLSelection};
+///
+/// let ll_line = create_line_from_string("one two").run(&resolver_fn(|sel: LLSelection| {
+///     sel.find_by(&x::token_text())
+///         .finish_with(|text| text.len())
+/// }));
+///
+/// assert_eq!(ll_line.query::<usize>().len(), 3);
+/// ```
+pub fn resolver_fn<F, A>(f: F) -> FnResolver<F, A>
+where
+    F: Fn(LLSelection) -> Vec<LLCursorAssignment<A>>,
+{
+    FnResolver {
+        f,
+        _attr: PhantomData,
+    }
+}
+
+impl<F, A> Resolver for FnResolver<F, A>
+where
+    F: Fn(LLSelection) -> Vec<LLCursorAssignment<A>>,
+    A: std::fmt::Debug + Clone + Send + 'static,
+{
+    type Attr = A;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        (self.f)(selection)
+    }
+}
@@ -3,7 +3,11 @@ use unicode_width::UnicodeWidthStr;
 
 pub struct LLLineDisplay<'a> {
     ll_line: &'a LLLine,
-    include_attrs: Vec<(LRange, String)>,
+    /// The rows of each included type, from top to bottom
+    layers: Vec<(TypeId, Vec<(LRange, String)>)>,
+    /// Set by [LLLineDisplay::only]
+    only: Option<Vec<TypeId>>,
+    excluded: Vec<TypeId>,
 }
 
 // 0,  1,     2,   3, - LRange indexes
@@ -43,16 +47,19 @@ impl<'a> std::fmt::Display for LLLineDisplay<'a> {
                 if is_first {
                     is_first = false;
                 } else {
-                    opening_line.extend(std::iter::repeat(' ').take(SPACE_PADDING));
+                    opening_line.extend(std::iter::repeat_n(' ', SPACE_PADDING));
                 }
 
                 token_idx_to_start_display_char_idx.push(UnicodeWidthStr::width(&*opening_line));
 
                 match &ll_token.token {
                     LToken::Text(text, _) => {
-                        opening_line.push_str(&text);
+                        opening_line.push_str(text);
                     }
-                    LToken::Value { .. } => {
+                    LToken::Value(token_value) if !token_value.placeholder().is_empty() => {
+                        opening_line.push_str(token_value.placeholder());
+                    }
+                    LToken::Value(_) => {
                         write!(&mut opening_line, "<>")?;
                     }
                 }
@@ -66,7 +73,12 @@ impl<'a> std::fmt::Display for LLLineDisplay<'a> {
         // ex:
         //     ╰────────────╯ Amount(..)
         //                            ╰─╯ Amount(..)
-        for ((starts_at_token_idx, ends_at_token_idx), debug_value) in self.include_attrs.iter() {
+        let shown_attrs = self
+            .layers
+            .iter()
+            .filter(|(type_id, _)| self.is_shown(*type_id))
+            .flat_map(|(_, attrs)| attrs);
+        for ((starts_at_token_idx, ends_at_token_idx), debug_value) in shown_attrs {
             f.write_char('\n')?;
 
             let start_char_idx = token_idx_to_start_display_char_idx[*starts_at_token_idx];
@@ -86,7 +98,7 @@ impl<'a> std::fmt::Display for LLLineDisplay<'a> {
                 f.write_char('╯')?;
             }
 
-            f.write_str(&debug_value)?;
+            f.write_str(debug_value)?;
         }
 
         Ok(())
@@ -97,28 +109,95 @@ impl<'a> LLLineDisplay<'a> {
     pub fn new(ll_line: &'a LLLine) -> Self {
         LLLineDisplay {
             ll_line,
-            include_attrs: Vec::new(),
+            layers: Vec::new(),
+            only: None,
+            excluded: Vec::new(),
         }
     }
-    // TODO consider making this method take and return `self`
-    pub fn include<T: 'static + std::fmt::Debug>(&mut self) {
-        for ll_range in self.ll_line.attrs.ranges.get::<T>() {
+
+    fn is_shown(&self, type_id: TypeId) -> bool {
+        !self.excluded.contains(&type_id)
+            && self
+                .only
+                .as_ref()
+                .is_none_or(|only| only.contains(&type_id))
+    }
+
+    fn include_any(&mut self, type_id: TypeId) {
+        let mut attrs = Vec::new();
+        for ll_range in self.ll_line.attrs.ranges.get_any(type_id) {
             for debug_value in self
                 .ll_line
                 .attrs
                 .values
                 .get(ll_range)
                 .into_iter()
-                .flat_map(|type_bucket| type_bucket.get_debug::<T>())
+                .flat_map(|type_bucket| type_bucket.get_debug_any(type_id))
                 .rev()
             {
-                self.include_attrs.push((*ll_range, debug_value));
+                attrs.push((*ll_range, debug_value));
             }
         }
+        self.layers.push((type_id, attrs));
+    }
+
+    // TODO consider making this method take and return `self`
+    pub fn include<T: 'static + std::fmt::Debug>(&mut self) {
+        self.include_any(TypeId::of::<T>());
     }
     /// Takes self
     pub fn with<T: 'static + std::fmt::Debug>(mut self) -> Self {
         self.include::<T>();
         self
     }
+
+    /// Include the attributes of every type not included yet, including the
+    /// `char` and [TextTag] assigned to each token. The new layers are ordered
+    /// by type name, see [LLLineDisplay::order_layers_by] to change it.
+    pub fn include_all(&mut self) {
+        let mut type_ids: Vec<(&str, TypeId)> = self
+            .ll_line
+            .attrs
+            .ranges
+            .iter()
+            .filter(|(type_id, _)| !self.layers.iter().any(|(layer, _)| layer == type_id))
+            .filter_map(|(type_id, ranges)| {
+                let name = self.ll_line.type_name(ranges.first()?, type_id);
+                Some((name, type_id))
+            })
+            .collect();
+        type_ids.sort_unstable();
+
+        for (_, type_id) in type_ids {
+            self.include_any(type_id);
+        }
+    }
+    /// Takes self
+    pub fn with_all(mut self) -> Self {
+        self.include_all();
+        self
+    }
+
+    /// Only show the attributes of `T`, or of every type passed to `only`
+    /// when called more than once, out of the included types.
+    pub fn only<T: 'static>(mut self) -> Self {
+        self.only
+            .get_or_insert_with(Vec::new)
+            .push(TypeId::of::<T>());
+        self
+    }
+
+    /// Hide the attributes of `T`, even if included before or after.
+    pub fn exclude<T: 'static>(mut self) -> Self {
+        self.excluded.push(TypeId::of::<T>());
+        self
+    }
+
+    /// Stack the layers included so far by `key` of their type, from top to
+    /// bottom, instead of the order they were included in. Layers with equal
+    /// keys keep their order.
+    pub fn order_layers_by<K: Ord>(mut self, mut key: impl FnMut(TypeId) -> K) -> Self {
+        self.layers.sort_by_key(|(type_id, _)| key(*type_id));
+        self
+    }
 }
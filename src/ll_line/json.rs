@@ -0,0 +1,234 @@
+use super::{LLLine, LLLineAttrs, LLToken, LRange, TextTag};
+use crate::type_bucket::TypeBucket;
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Serialize};
+use std::any::TypeId;
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+/// Caching of the tokenization only.
+///
+/// Resolvers are cheap to re-run compared to splitting the text, so this
+/// leaves out every attribute. [LLLine::tokens_from_json] assigns the `char`
+/// and [TextTag] attributes again, but custom attributes given to
+/// [crate::InputToken] are not restored, see [LLLine::to_json] for that.
+impl LLLine {
+    pub fn tokens_to_json(&self) -> String {
+        serde_json::to_string(&self.ll_tokens).expect("tokens can be serialized")
+    }
+
+    pub fn tokens_from_json(json: &str) -> serde_json::Result<LLLine> {
+        let ll_tokens: Vec<LLToken> = serde_json::from_str(json)?;
+        check_token_idx(&ll_tokens)?;
+
+        Ok(LLLine::new(ll_tokens))
+    }
+}
+
+/// The attribute types saved and restored by [LLLine::to_json] and
+/// [LLLine::from_json], each under a name which must stay the same between
+/// both.
+///
+/// `char` and [TextTag] are registered as `"char"` and `"TextTag"`.
+pub struct AttrRegistry {
+    types: Vec<RegisteredType>,
+}
+
+struct RegisteredType {
+    name: String,
+    type_id: TypeId,
+    /// The values of the type in a bucket
+    to_json: fn(&TypeBucket) -> serde_json::Result<serde_json::Value>,
+    /// Inserts values of the type at a range
+    insert_json: fn(&mut LLLineAttrs, LRange, serde_json::Value) -> serde_json::Result<()>,
+}
+
+impl AttrRegistry {
+    pub fn new() -> Self {
+        AttrRegistry { types: Vec::new() }
+            .register::<char>("char")
+            .register::<TextTag>("TextTag")
+    }
+
+    /// Saves and restores the `T` attributes under `name`.
+    ///
+    /// # Panics
+    ///
+    /// If `T` or `name` is registered already.
+    pub fn register<T>(mut self, name: &str) -> Self
+    where
+        T: 'static + Debug + Clone + Send + Serialize + DeserializeOwned,
+    {
+        assert!(
+            self.types.iter().all(
+                |registered| registered.name != name && registered.type_id != TypeId::of::<T>()
+            ),
+            "attribute type registered once"
+        );
+
+        self.types.push(RegisteredType {
+            name: name.to_string(),
+            type_id: TypeId::of::<T>(),
+            to_json: |bucket| serde_json::to_value(bucket.get::<T>()),
+            insert_json: |attrs, range, json| {
+                for value in serde_json::from_value::<Vec<T>>(json)? {
+                    attrs.insert(range, value);
+                }
+                Ok(())
+            },
+        });
+        self
+    }
+}
+
+impl Default for AttrRegistry {
+    fn default() -> Self {
+        AttrRegistry::new()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct LineJson<'a> {
+    tokens: Cow<'a, [LLToken]>,
+    attrs: Vec<TypeAttrsJson>,
+}
+
+/// The attributes of a type, in the order they were assigned
+#[derive(Serialize, Deserialize)]
+struct TypeAttrsJson {
+    #[serde(rename = "type")]
+    type_name: String,
+    assigned: Vec<AssignedJson>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AssignedJson {
+    range: LRange,
+    values: serde_json::Value,
+    #[serde(default, skip_serializing_if = "is_default_priority")]
+    priority: i32,
+}
+
+fn is_default_priority(priority: &i32) -> bool {
+    *priority == 0
+}
+
+/// Caching of resolved lines.
+///
+/// Only the attributes of the types in the registry are saved, and restored
+/// in the order they were assigned, so a restored line has the same `query`
+/// results and matches as the original.
+///
+/// The [crate::Metadata] of the values is not saved, the values of a
+/// restored line have none.
+impl LLLine {
+    pub fn to_json(&self, registry: &AttrRegistry) -> String {
+        let attrs = registry
+            .types
+            .iter()
+            .filter_map(|registered| {
+                let ranges = self.attrs.ranges.get_any(registered.type_id);
+                if ranges.is_empty() {
+                    return None;
+                }
+
+                let assigned = ranges
+                    .iter()
+                    .map(|range| AssignedJson {
+                        range: *range,
+                        values: (registered.to_json)(&self.attrs.values[range])
+                            .expect("registered attributes can be serialized"),
+                        priority: self
+                            .attrs
+                            .priorities
+                            .get(&(*range, registered.type_id))
+                            .copied()
+                            .unwrap_or(0),
+                    })
+                    .collect();
+
+                Some(TypeAttrsJson {
+                    type_name: registered.name.clone(),
+                    assigned,
+                })
+            })
+            .collect();
+
+        serde_json::to_string(&LineJson {
+            tokens: Cow::Borrowed(&self.ll_tokens),
+            attrs,
+        })
+        .expect("line can be serialized")
+    }
+
+    /// Restores a line saved by [LLLine::to_json], with a registry of at
+    /// least the same types.
+    pub fn from_json(json: &str, registry: &AttrRegistry) -> serde_json::Result<LLLine> {
+        let LineJson { tokens, attrs } = serde_json::from_str(json)?;
+        let ll_tokens = tokens.into_owned();
+        check_token_idx(&ll_tokens)?;
+
+        let mut line_attrs = LLLineAttrs::with_len(ll_tokens.len());
+        // every token has a bucket, even when its attributes were removed or
+        // not registered
+        for token_idx in 0..ll_tokens.len() {
+            line_attrs.values.entry((token_idx, token_idx)).or_default();
+        }
+
+        for TypeAttrsJson {
+            type_name,
+            assigned,
+        } in attrs
+        {
+            let registered = registry
+                .types
+                .iter()
+                .find(|registered| registered.name == type_name)
+                .ok_or_else(|| {
+                    serde_json::Error::custom(format!(
+                        "unregistered attribute type {:?}",
+                        type_name
+                    ))
+                })?;
+
+            for AssignedJson {
+                range,
+                values,
+                priority,
+            } in assigned
+            {
+                if range.0 > range.1 || range.1 >= ll_tokens.len() {
+                    return Err(serde_json::Error::custom(format!(
+                        "range {:?} out of the line",
+                        range
+                    )));
+                }
+
+                (registered.insert_json)(&mut line_attrs, range, values)?;
+                if priority != 0 {
+                    line_attrs
+                        .priorities
+                        .insert((range, registered.type_id), priority);
+                }
+            }
+        }
+
+        Ok(LLLine {
+            ll_tokens,
+            attrs: line_attrs,
+        })
+    }
+}
+
+fn check_token_idx(ll_tokens: &[LLToken]) -> serde_json::Result<()> {
+    for (idx, ll_token) in ll_tokens.iter().enumerate() {
+        if ll_token.token_idx != idx {
+            return Err(serde_json::Error::custom(format!(
+                "token at index {} has token_idx {}",
+                idx, ll_token.token_idx
+            )));
+        }
+    }
+
+    Ok(())
+}
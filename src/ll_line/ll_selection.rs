@@ -1,5 +1,8 @@
 use super::x::{XBackwards, XForwards};
-use super::{assert_ll_lines_equals, LLCursorAssignment, LLLine, Rc, XMatch};
+use super::{
+    assert_ll_lines_equals, AssignmentValue, LLCursorAssignment, LLLine, LRange, LToken,
+    PositionRange, Rc, TextTag, WithMetadata, XMatch,
+};
 
 // # List of operations
 //
@@ -92,7 +95,7 @@ impl LLSelection {
         let end_opt = self.selection_from(matches.last().unwrap().0.end_idx + 1, self.end_idx);
         start_opt
             .into_iter()
-            .chain(matches.windows(2).into_iter().filter_map(|m| {
+            .chain(matches.windows(2).filter_map(|m| {
                 m[1].0
                     .start_idx
                     .checked_sub(1)
@@ -102,9 +105,56 @@ impl LLSelection {
             .collect()
     }
 
+    /// The spans between the `T` attributes of this selection, like the items
+    /// of `red, green, and blue` split on a `Comma` attribute. Empty spans, as
+    /// between two adjacent delimiters, are skipped, see
+    /// [LLSelection::split_on_keeping_empty].
+    pub fn split_on<T: 'static>(&self) -> Vec<LLSelection> {
+        self.split_on_keeping_empty::<T>()
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    /// Like [LLSelection::split_on], with `None` for each empty span, so
+    /// `n` delimiters always give `n + 1` spans. Overlapping `T` attributes
+    /// count as a single delimiter.
+    pub fn split_on_keeping_empty<T: 'static>(&self) -> Vec<Option<LLSelection>> {
+        let mut delimiters: Vec<LRange> = self
+            .ll_line
+            .attrs
+            .ranges
+            .get::<T>()
+            .iter()
+            .filter(|range| range.0 <= self.end_idx && range.1 >= self.start_idx)
+            .map(|range| (range.0.max(self.start_idx), range.1.min(self.end_idx)))
+            .collect();
+        delimiters.sort_unstable();
+
+        let mut spans = Vec::new();
+        let mut span_start_idx = self.start_idx;
+        for (start_idx, end_idx) in delimiters {
+            // overlaps the previous delimiter
+            if end_idx < span_start_idx {
+                continue;
+            }
+            if start_idx >= span_start_idx {
+                spans.push(
+                    start_idx
+                        .checked_sub(1)
+                        .and_then(|span_end_idx| self.selection_from(span_start_idx, span_end_idx)),
+                );
+            }
+            span_start_idx = end_idx + 1;
+        }
+        spans.push(self.selection_from(span_start_idx, self.end_idx));
+
+        spans
+    }
+
     pub fn find_by<'a, M: XMatch<'a>>(&'a self, matcher: &M) -> Vec<(LLSelection, M::Out)> {
         (self.start_idx..=self.end_idx)
-            .map(|i| {
+            .flat_map(|i| {
                 let forwards = XForwards { from_idx: i };
 
                 matcher
@@ -121,7 +171,6 @@ impl LLSelection {
                         )
                     })
             })
-            .flatten()
             .collect()
     }
 
@@ -137,7 +186,7 @@ impl LLSelection {
         matcher: &M,
     ) -> Vec<(LLSelection, M::Out)> {
         (self.start_idx..=self.end_idx)
-            .map(|i| {
+            .flat_map(|i| {
                 let forwards = XForwards { from_idx: i };
 
                 matcher
@@ -170,7 +219,6 @@ impl LLSelection {
                         )
                     })
             })
-            .flatten()
             .collect()
     }
 
@@ -254,6 +302,50 @@ impl LLSelection {
         self.match_backwards(matcher).into_iter().next()
     }
 
+    /// (start, end) token indexes covered by this selection, both inclusive
+    pub fn token_range(&self) -> (usize, usize) {
+        (self.start_idx, self.end_idx)
+    }
+
+    /// (start, end) positions covered by this selection, in the sizes given
+    /// when creating the line, the end being exclusive like
+    /// [super::LLToken::pos_ends_at]
+    pub fn range(&self) -> PositionRange {
+        (
+            self.ll_line.pos_start_at(self.start_idx),
+            self.ll_line.pos_end_at(self.end_idx),
+        )
+    }
+
+    /// (start, end) byte offsets covered by this selection in the line text,
+    /// see [LLLine::byte_range]
+    pub fn byte_range(&self) -> (usize, usize) {
+        self.ll_line.byte_range((self.start_idx, self.end_idx))
+    }
+
+    /// The text covered by this selection, like [LLLine::query] gives it:
+    /// value tokens are written as their [super::TokenValue::placeholder].
+    ///
+    /// Tokens keep their own text, so it is put together in a new `String`.
+    /// [super::x::token_text] borrows the text of a single token instead.
+    pub fn text(&self) -> String {
+        self.ll_line.text((self.start_idx, self.end_idx))
+    }
+
+    /// Whether only whitespace comes before this selection in the line
+    pub fn at_line_start(&self) -> bool {
+        self.ll_line.ll_tokens[..self.start_idx]
+            .iter()
+            .all(|ll_token| matches!(ll_token.token, LToken::Text(_, TextTag::SPACE)))
+    }
+
+    /// Whether only whitespace comes after this selection in the line
+    pub fn at_line_end(&self) -> bool {
+        self.ll_line.ll_tokens[self.end_idx + 1..]
+            .iter()
+            .all(|ll_token| matches!(ll_token.token, LToken::Text(_, TextTag::SPACE)))
+    }
+
     pub fn after(&self) -> Option<LLSelection> {
         let ll_line_end = self.ll_line.ll_tokens.len() - 1;
 
@@ -282,6 +374,28 @@ impl LLSelection {
         }
     }
 
+    /// From the start of this selection to the end of `other_selection`,
+    /// whatever the tokens in between, like from the first to the last of
+    /// several matches.
+    ///
+    /// # Panics
+    ///
+    /// If `other_selection` is of another line, or ends before this
+    /// selection starts.
+    pub fn extend_to(&self, other_selection: &LLSelection) -> LLSelection {
+        assert_ll_lines_equals(&self.ll_line, &other_selection.ll_line);
+        assert!(
+            other_selection.end_idx >= self.start_idx,
+            "other selection ends after this one starts"
+        );
+
+        LLSelection {
+            ll_line: self.ll_line.clone(),
+            start_idx: self.start_idx,
+            end_idx: other_selection.end_idx,
+        }
+    }
+
     // Hmmm... TODO: Unit test this thoroughly
     pub fn split_with(&self, other_selection: &LLSelection) -> [Option<LLSelection>; 2] {
         assert_ll_lines_equals(&self.ll_line, &other_selection.ll_line);
@@ -373,7 +487,17 @@ impl LLSelection {
         LLCursorAssignment {
             end_idx: self.end_idx,
             start_idx: self.start_idx,
-            value,
+            value: AssignmentValue::Assign(value),
+            priority: 0,
+            metadata: None,
         }
     }
+
+    /// The `T` values assigned to exactly the range of this selection, with
+    /// their [super::Metadata], to pick between competing values
+    pub fn attrs_with_metadata<T: 'static>(&self) -> WithMetadata<'_, T> {
+        self.ll_line
+            .attrs
+            .get_with_metadata((self.start_idx, self.end_idx))
+    }
 }
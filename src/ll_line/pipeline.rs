@@ -0,0 +1,84 @@
+use super::{LLLine, Resolver};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// [Resolver] with its attribute type erased, to keep resolvers of
+/// different types together
+trait AnyResolver {
+    fn run_on(&self, ll_line: LLLine) -> LLLine;
+}
+
+impl<R: Resolver> AnyResolver for R {
+    fn run_on(&self, ll_line: LLLine) -> LLLine {
+        ll_line.run(self)
+    }
+}
+
+/// Resolvers of any attribute types, run in order with [LLLine::run_all].
+///
+/// Running a pipeline is the same as chaining [LLLine::run] calls, so each
+/// resolver sees the attributes assigned by the resolvers before it.
+///
+/// Every pipeline requires its resolvers to be `Send + Sync`, with or
+/// without the `rayon` feature, so that a pipeline can run over many lines
+/// at once with `ResolverPipeline::run_batch`. A resolver holding a
+/// `RefCell` or an `Rc`, like a [crate::resolver_fn] capturing one, can't
+/// be added: chain [LLLine::run] calls instead, or use a `Mutex` or an
+/// `Arc`.
+///
+/// ```
+/// use layered_nlp::{create_line_from_string, resolver_fn, x, FinishWith, LLSelection};
+/// use layered_nlp::{ResolverPipeline, TextTag};
+///
+/// let pipeline = ResolverPipeline::new()
+///     .with(resolver_fn(|sel: LLSelection| {
+///         sel.find_by(&x::attr_eq(&TextTag::NATN))
+///             .finish_with(|_| 'n')
+///     }))
+///     .with(resolver_fn(|sel: LLSelection| {
+///         sel.find_by(&x::attr::<char>())
+///             .finish_with(|ch| ch.to_string())
+///     }));
+///
+/// let ll_line = create_line_from_string("1 2").run_all(&pipeline);
+/// assert_eq!(ll_line.query::<String>().len(), 3);
+/// ```
+#[derive(Default)]
+pub struct ResolverPipeline {
+    resolvers: Vec<Box<dyn AnyResolver + Send + Sync>>,
+}
+
+impl ResolverPipeline {
+    pub fn new() -> Self {
+        ResolverPipeline::default()
+    }
+
+    /// Run `resolver` after the resolvers added so far. `resolver` must be
+    /// `Send + Sync`, see [ResolverPipeline].
+    pub fn push<R: Resolver + Send + Sync + 'static>(&mut self, resolver: R) {
+        self.resolvers.push(Box::new(resolver));
+    }
+
+    /// Takes self
+    pub fn with<R: Resolver + Send + Sync + 'static>(mut self, resolver: R) -> Self {
+        self.push(resolver);
+        self
+    }
+
+    pub(super) fn run(&self, ll_line: LLLine) -> LLLine {
+        self.resolvers
+            .iter()
+            .fold(ll_line, |ll_line, resolver| resolver.run_on(ll_line))
+    }
+
+    /// Runs the pipeline on each line, spreading the lines over the threads
+    /// of the rayon pool. Returns the lines in the same order, as they would
+    /// be after [LLLine::run_all].
+    #[cfg(feature = "rayon")]
+    pub fn run_batch(&self, lines: Vec<LLLine>) -> Vec<LLLine> {
+        lines
+            .into_par_iter()
+            .map(|ll_line| self.run(ll_line))
+            .collect()
+    }
+}
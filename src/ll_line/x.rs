@@ -1,10 +1,46 @@
 //! # Matcher pieces
+//!
+//! Matchers are built with the functions of this module, like [attr] or
+//! [seq], and used by [crate::LLSelection] and [LLLine::find].
+//!
+//! Your own matchers implement [XMatch], usually on top of the
+//! [XDirection] methods so they work both forwards and backwards:
+//!
+//! ```
+//! use layered_nlp::x::{self, ToIdx, XDirection, XMatch};
+//! use layered_nlp::{create_line_from_string, LLLine};
+//!
+//! /// Matches a token written in capitals, like `NASA`
+//! struct Capitals;
+//!
+//! impl<'l> XMatch<'l> for Capitals {
+//!     type Out = &'l str;
+//!
+//!     fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+//!     where
+//!         M: XDirection<'l>,
+//!     {
+//!         direction
+//!             .text_token(ll_line)
+//!             .filter(|(text, _)| text.chars().all(char::is_uppercase))
+//!             .into_iter()
+//!             .collect()
+//!     }
+//! }
+//!
+//! let ll_line = create_line_from_string("NASA and ESA");
+//! assert_eq!(ll_line.find(&Capitals).len(), 2);
+//! // and they compose with the other matchers
+//! assert_eq!(ll_line.find(&x::seq((Capitals, x::whitespace()))).len(), 1);
+//! ```
 
 mod all;
 mod any_of;
 mod attr;
 mod attr_eq;
+mod attr_matches;
 mod functions;
+mod opt;
 mod seq;
 mod token_has_any;
 mod token_text;
@@ -13,14 +49,21 @@ pub use all::{All, All2, All3};
 pub use any_of::{AnyOf, AnyOf2, AnyOf2Matcher, AnyOf3, AnyOf3Matcher};
 pub use attr::Attr;
 pub use attr_eq::AttrEq;
-pub use functions::{all, any_of, attr, attr_eq, seq, token_has_any, token_text, whitespace};
+pub use attr_matches::{AttrMatches, XAttrMatches};
+pub use functions::{
+    all, any_of, attr, attr_eq, attr_matches, opt, seq, token_has_any, token_text, whitespace,
+};
+pub use opt::Opt;
 pub use seq::{Seq, Seq2, Seq3};
 pub use token_has_any::TokenHasAny;
 pub use token_text::TokenText;
 
 use super::{LLLine, LLToken, LToken};
 
-/// Examples: Attr, AttrEq
+/// Matches tokens from a position of a line, in a direction.
+///
+/// Examples: Attr, AttrEq, XAttrMatches, and the module documentation for a
+/// custom matcher.
 pub trait XMatch<'l> {
     /// Usually must be [Copy] so it's compatible with any multi-matchers.
     /// The Out must be copied in the event of "cartesian" product scenarios where multi-matchers
@@ -30,14 +73,41 @@ pub trait XMatch<'l> {
     /// like `&'l Tag`, and all references in Rust are [Copy].
     type Out: Copy;
 
+    /// Every match starting at the token [XDirection::idx], along with
+    /// the index of the last token it covers: the end of the match going
+    /// forwards, its start going backwards.
     fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
     where
         M: XDirection<'l>;
+
+    /// The output of matching no tokens, for the matchers which may, like
+    /// [opt]. [seq] skips over them with this output when they don't match.
+    ///
+    /// [XMatch::go] only returns the matches covering tokens, as selections
+    /// are never empty.
+    fn empty(&self) -> Option<Self::Out> {
+        None
+    }
 }
 
-#[derive(PartialEq, Eq, Clone, Copy)]
+/// Index of the last token covered by a match, in the direction of the match
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct ToIdx(pub(crate) usize);
 
+impl ToIdx {
+    pub fn new(idx: usize) -> Self {
+        ToIdx(idx)
+    }
+
+    pub fn idx(&self) -> usize {
+        self.0
+    }
+}
+
+/// Where and which way a matcher is matching, implemented by [XForwards] and [XBackwards].
+///
+/// The provided methods find what the built-in matchers need, from the
+/// token at [XDirection::idx].
 pub trait XDirection<'l>
 where
     Self: Sized,
@@ -49,8 +119,13 @@ where
     // fn from_idx(ll_line: &'l LLLine, idx: usize) -> Result<Self, OutOfBoundsError> {
     //     todo!()
     // }
+    /// Index of the first token to match
+    fn idx(&self) -> usize;
+    /// `T` attributes whose range starts at [XDirection::idx] (ends, going backwards)
     fn attr<T: 'static>(&self, ll_line: &'l LLLine) -> Vec<(&'l T, ToIdx)>;
+    /// Like [XDirection::attr], for the values equal to `equals`
     fn attr_eq<T: 'static + PartialEq>(&self, equals: &T, ll_line: &'l LLLine) -> Vec<((), ToIdx)>;
+    /// `T` attributes of the single token at [XDirection::idx] which are in `set`
     fn token_attr_one_of<T: 'static + PartialEq>(
         &self,
         set: &[T],
@@ -58,12 +133,15 @@ where
     ) -> Vec<(&'l T, ToIdx)>;
     /// If the next token is Text, return the inner string slice
     fn text_token(&self, ll_line: &'l LLLine) -> Option<(&'l str, ToIdx)>;
+    /// The direction to continue matching after a match ending at `idx`,
+    /// `None` at the edge of the line
     fn after(&self, idx: usize, ll_line: &'l LLLine) -> Option<Self>
     where
         Self: Sized;
 }
 
-pub(crate) struct XForwards {
+/// Matching towards the end of the line
+pub struct XForwards {
     pub(super) from_idx: usize,
 }
 
@@ -84,6 +162,10 @@ pub(crate) struct XForwards {
 // }
 
 impl<'l> XDirection<'l> for XForwards {
+    fn idx(&self) -> usize {
+        self.from_idx
+    }
+
     fn attr_eq<T: 'static + PartialEq>(&self, equals: &T, ll_line: &'l LLLine) -> Vec<((), ToIdx)> {
         ll_line
             .attrs
@@ -96,7 +178,7 @@ impl<'l> XDirection<'l> for XForwards {
                 ll_line
                     .attrs
                     .values
-                    .get(&range)
+                    .get(range)
                     .unwrap()
                     .get::<T>()
                     .iter()
@@ -123,7 +205,7 @@ impl<'l> XDirection<'l> for XForwards {
                 ll_line
                     .attrs
                     .values
-                    .get(&range)
+                    .get(range)
                     .unwrap()
                     .get::<T>()
                     .iter()
@@ -182,11 +264,16 @@ impl<'l> XDirection<'l> for XForwards {
     }
 }
 
-pub(crate) struct XBackwards {
+/// Matching towards the start of the line
+pub struct XBackwards {
     pub(super) from_idx: usize,
 }
 
 impl<'l> XDirection<'l> for XBackwards {
+    fn idx(&self) -> usize {
+        self.from_idx
+    }
+
     fn attr_eq<T: 'static + PartialEq>(&self, equals: &T, ll_line: &'l LLLine) -> Vec<((), ToIdx)> {
         //        [ ... ] - Current Selection
         // [ ... ] - Trying to match Attr
@@ -202,7 +289,7 @@ impl<'l> XDirection<'l> for XBackwards {
                 ll_line
                     .attrs
                     .values
-                    .get(&range)
+                    .get(range)
                     .unwrap()
                     .get::<T>()
                     .iter()
@@ -232,7 +319,7 @@ impl<'l> XDirection<'l> for XBackwards {
                 ll_line
                     .attrs
                     .values
-                    .get(&range)
+                    .get(range)
                     .unwrap()
                     .get::<T>()
                     .iter()
@@ -260,12 +347,12 @@ impl<'l> XDirection<'l> for XBackwards {
                 ll_line
                     .attrs
                     .values
-                    .get(&range)
+                    .get(range)
                     .unwrap()
                     .get::<T>()
                     .iter()
                     .filter_map(move |val| {
-                        if set.contains(&val) {
+                        if set.contains(val) {
                             Some((val, ToIdx(range.0)))
                         } else {
                             None
@@ -281,7 +368,7 @@ impl<'l> XDirection<'l> for XBackwards {
             .get(self.from_idx)
             .and_then(|token| match &token.token {
                 LToken::Text(text, _) => Some((text.as_str(), ToIdx(self.from_idx))),
-                LToken::Value => None,
+                LToken::Value(_) => None,
             })
     }
 
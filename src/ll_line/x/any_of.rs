@@ -49,6 +49,13 @@ impl<'l, A: XMatch<'l>, B: XMatch<'l>> XMatch<'l> for AnyOf2Matcher<A, B> {
                 .collect()
         }
     }
+
+    fn empty(&self) -> Option<Self::Out> {
+        self.0
+            .empty()
+            .map(AnyOf2::A)
+            .or_else(|| self.1.empty().map(AnyOf2::B))
+    }
 }
 
 pub struct AnyOf3Matcher<A, B, C>(pub A, pub B, pub C);
@@ -85,4 +92,12 @@ impl<'l, A: XMatch<'l>, B: XMatch<'l>, C: XMatch<'l>> XMatch<'l> for AnyOf3Match
             }
         }
     }
+
+    fn empty(&self) -> Option<Self::Out> {
+        self.0
+            .empty()
+            .map(AnyOf3::A)
+            .or_else(|| self.1.empty().map(AnyOf3::B))
+            .or_else(|| self.2.empty().map(AnyOf3::C))
+    }
 }
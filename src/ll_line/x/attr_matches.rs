@@ -0,0 +1,36 @@
+use super::{LLLine, ToIdx, XDirection, XMatch};
+
+/// Domain-specific equality used by [XAttrMatches].
+///
+/// Every [PartialEq] type gets this through a blanket impl, so only attribute
+/// types that need fuzzier matching (and don't implement [PartialEq]) have to
+/// implement it by hand.
+pub trait AttrMatches {
+    /// Whether the `candidate` found on the line should be considered a match for `self`.
+    fn matches(&self, candidate: &Self) -> bool;
+}
+
+impl<T: PartialEq> AttrMatches for T {
+    fn matches(&self, candidate: &Self) -> bool {
+        self == candidate
+    }
+}
+
+pub struct XAttrMatches<'a, Attr> {
+    pub(crate) attr: &'a Attr,
+}
+
+impl<'l, Attr: AttrMatches + 'static> XMatch<'l> for XAttrMatches<'_, Attr> {
+    type Out = &'l Attr;
+
+    fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        direction
+            .attr::<Attr>(ll_line)
+            .into_iter()
+            .filter(|(candidate, _)| self.attr.matches(candidate))
+            .collect()
+    }
+}
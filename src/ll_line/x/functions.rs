@@ -24,6 +24,13 @@ pub fn seq<T: Seq>(tuple: T) -> T::Out {
     tuple.into_seq()
 }
 
+/// Match `matcher`, or nothing with `None` as part of a [seq]
+///
+/// Going forward, `seq((a, opt(b), c))` matches `a b c` and `a c`.
+pub fn opt<M>(matcher: M) -> Opt<M> {
+    Opt(matcher)
+}
+
 /// Match single token and provide their text representation
 pub fn token_text() -> TokenText {
     TokenText(())
@@ -34,6 +41,11 @@ pub fn attr_eq<A>(attr: &A) -> AttrEq<'_, A> {
     AttrEq { attr }
 }
 
+/// Match token with `A` attributes for which `attr` [AttrMatches::matches]
+pub fn attr_matches<A: AttrMatches>(attr: &A) -> XAttrMatches<'_, A> {
+    XAttrMatches { attr }
+}
+
 /// Match token with `A` attributes equals to one of `attrs` value
 pub fn token_has_any<A: PartialEq>(attrs: &[A]) -> TokenHasAny<'_, A> {
     TokenHasAny { one_of: attrs }
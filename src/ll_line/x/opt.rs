@@ -0,0 +1,22 @@
+use super::{LLLine, ToIdx, XDirection, XMatch};
+
+pub struct Opt<M>(pub M);
+
+impl<'l, X: XMatch<'l>> XMatch<'l> for Opt<X> {
+    type Out = Option<X::Out>;
+
+    fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        self.0
+            .go(direction, ll_line)
+            .into_iter()
+            .map(|(out, to_idx)| (Some(out), to_idx))
+            .collect()
+    }
+
+    fn empty(&self) -> Option<Self::Out> {
+        Some(None)
+    }
+}
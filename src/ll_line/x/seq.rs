@@ -22,6 +22,41 @@ impl<A, B, C> Seq for (A, B, C) {
     }
 }
 
+/// Outputs of a sequence so far, and the index of its last token
+type Partial<T> = Vec<(T, Option<ToIdx>)>;
+
+/// Continues each of the sequences `before` with the matches of `matcher`
+/// following it, and with the empty match of `matcher` if it has one.
+///
+/// A `None` index is a sequence which matched no tokens yet, continued from
+/// the start of `direction`.
+fn then<'l, T: Copy, X: XMatch<'l>, M: XDirection<'l>>(
+    before: Partial<T>,
+    matcher: &X,
+    direction: &M,
+    ll_line: &'l LLLine,
+) -> Partial<(T, X::Out)> {
+    let empty = matcher.empty();
+
+    before
+        .into_iter()
+        .flat_map(|(t, to_idx)| {
+            let found = match to_idx {
+                Some(to_idx) => direction
+                    .after(to_idx.0, ll_line)
+                    .map(|direction| matcher.go(&direction, ll_line))
+                    .unwrap_or_else(Vec::new),
+                None => matcher.go(direction, ll_line),
+            };
+
+            found
+                .into_iter()
+                .map(move |(out, to_idx)| ((t, out), Some(to_idx)))
+                .chain(empty.map(|out| ((t, out), to_idx)))
+        })
+        .collect()
+}
+
 pub struct Seq2<A, B>(pub A, pub B);
 
 impl<'l, A: XMatch<'l>, B: XMatch<'l>> XMatch<'l> for Seq2<A, B> {
@@ -31,19 +66,18 @@ impl<'l, A: XMatch<'l>, B: XMatch<'l>> XMatch<'l> for Seq2<A, B> {
     where
         M: XDirection<'l>,
     {
-        self.0
-            .go(direction, ll_line)
+        let found = then(vec![((), None)], &self.0, direction, ll_line);
+        let found = then(found, &self.1, direction, ll_line);
+
+        found
             .into_iter()
-            .flat_map(|(a, to_idx)| {
-                direction
-                    .after(to_idx.0, ll_line)
-                    .map(|direction| self.1.go(&direction, ll_line))
-                    .unwrap_or_else(Vec::new)
-                    .into_iter()
-                    .map(move |(b, to_idx)| ((a, b), to_idx))
-            })
+            .filter_map(|((((), a), b), to_idx)| Some(((a, b), to_idx?)))
             .collect()
     }
+
+    fn empty(&self) -> Option<Self::Out> {
+        Some((self.0.empty()?, self.1.empty()?))
+    }
 }
 
 pub struct Seq3<A, B, C>(pub A, pub B, pub C);
@@ -55,24 +89,17 @@ impl<'l, A: XMatch<'l>, B: XMatch<'l>, C: XMatch<'l>> XMatch<'l> for Seq3<A, B,
     where
         M: XDirection<'l>,
     {
-        self.0
-            .go(direction, ll_line)
+        let found = then(vec![((), None)], &self.0, direction, ll_line);
+        let found = then(found, &self.1, direction, ll_line);
+        let found = then(found, &self.2, direction, ll_line);
+
+        found
             .into_iter()
-            .flat_map(|(a, to_idx)| {
-                direction
-                    .after(to_idx.0, ll_line)
-                    .map(|direction| self.1.go(&direction, ll_line))
-                    .unwrap_or_else(Vec::new)
-                    .into_iter()
-                    .flat_map(move |(b, to_idx)| {
-                        direction
-                            .after(to_idx.0, ll_line)
-                            .map(|direction| self.2.go(&direction, ll_line))
-                            .unwrap_or_else(Vec::new)
-                            .into_iter()
-                            .map(move |(c, to_idx)| ((a, b, c), to_idx))
-                    })
-            })
+            .filter_map(|(((((), a), b), c), to_idx)| Some(((a, b, c), to_idx?)))
             .collect()
     }
+
+    fn empty(&self) -> Option<Self::Out> {
+        Some((self.0.empty()?, self.1.empty()?, self.2.empty()?))
+    }
 }
@@ -1,3 +1,31 @@
+mod boolean;
+mod coordination;
+mod date_range;
+mod dialogue_attribution;
+mod dimensions;
+mod emoticon;
+mod ip_address;
+mod key_value;
+mod nearby_repetition;
+mod quoted;
+mod reference;
+mod salutation;
+mod sentence;
 mod text_match;
+mod version;
 
+pub use boolean::{Bool, BooleanResolver};
+pub use coordination::{Coordination, CoordinationResolver};
+pub use date_range::{Date, DateRange, DateRangeResolver};
+pub use dialogue_attribution::{Attribution, DialogueAttributionResolver};
+pub use dimensions::{Dimensions, DimensionsResolver};
+pub use emoticon::{Emoticon, EmoticonResolver};
+pub use ip_address::{IpAddr, IpAddressResolver};
+pub use key_value::{KeyValue, KeyValueResolver};
+pub use nearby_repetition::{Echo, NearbyRepetitionResolver};
+pub use quoted::{Quoted, QuotedResolver};
+pub use reference::{Reference, ReferenceResolver};
+pub use salutation::{Salutation, SalutationKind, SalutationResolver};
+pub use sentence::{Sentence, SentenceResolver};
 pub use text_match::TextMatchAssignResolver;
+pub use version::{Version, VersionResolver};
@@ -0,0 +1,76 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// A word standing for a boolean, like `yes` or `disabled`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bool(pub bool);
+
+/// Marks affirmative and negative words with [Bool], ignoring case.
+pub struct BooleanResolver {
+    /// Lowercased words and their value
+    words: Vec<(String, bool)>,
+}
+
+impl BooleanResolver {
+    pub fn new(affirmative: &[&str], negative: &[&str]) -> Self {
+        BooleanResolver { words: Vec::new() }.with_words(affirmative, negative)
+    }
+
+    /// Recognize more words on top of the current ones.
+    pub fn with_words(mut self, affirmative: &[&str], negative: &[&str]) -> Self {
+        let lowercase = |words: &[&str], value: bool| -> Vec<(String, bool)> {
+            words
+                .iter()
+                .map(|word| (word.to_lowercase(), value))
+                .collect()
+        };
+
+        self.words.extend(lowercase(affirmative, true));
+        self.words.extend(lowercase(negative, false));
+        self
+    }
+}
+
+impl Default for BooleanResolver {
+    /// English "yes"/"no", "true"/"false", "on"/"off", "enabled"/"disabled" and the like.
+    fn default() -> Self {
+        BooleanResolver::new(
+            &["yes", "y", "true", "on", "enabled", "enable", "ok"],
+            &["no", "n", "false", "off", "disabled", "disable"],
+        )
+    }
+}
+
+impl Resolver for BooleanResolver {
+    type Attr = Bool;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .into_iter()
+            .filter_map(|(word_sel, (_, text))| {
+                let text = text.to_lowercase();
+                let (_, value) = self.words.iter().find(|(word, _)| *word == text)?;
+                Some(word_sel.finish_with_attr(Bool(*value)))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string("debug=TRUE, cache: disabled, beta yep, Nope")
+        .run(&BooleanResolver::default().with_words(&["yep"], &["nope"]));
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Bool>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    debug  =  TRUE  ,     cache  :     disabled  ,     beta     yep  ,     Nope
+              ╰──╯Bool(true)
+                                       ╰──────╯Bool(false)
+                                                                ╰─╯Bool(true)
+                                                                           ╰──╯Bool(false)
+    "###);
+}
@@ -0,0 +1,140 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// Two conjuncts joined by a coordinating conjunction, like `red apples and green pears`.
+///
+/// Assigned over the whole span, from the start of the left conjunct to the
+/// end of the right one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coordination {
+    /// (start, end) token indexes of the left conjunct
+    pub left_range: (usize, usize),
+    /// (start, end) token indexes of the right conjunct
+    pub right_range: (usize, usize),
+}
+
+/// Finds the conjuncts around each conjunction word with a symmetry heuristic.
+///
+/// Words are collected on both sides until punctuation, a symbol or another
+/// conjunction, then the longer side is cut down to as many words as the
+/// shorter one: `I like red apples and green pears` coordinates `red apples`
+/// with `green pears`. A comma right before the conjunction (`red, green, and
+/// blue`) is skipped.
+pub struct CoordinationResolver {
+    /// Lowercased conjunctions
+    conjunctions: Vec<String>,
+}
+
+impl CoordinationResolver {
+    pub fn new(conjunctions: &[&str]) -> Self {
+        CoordinationResolver {
+            conjunctions: conjunctions
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect(),
+        }
+    }
+
+    /// Selections reaching each word of the conjunct, from the nearest to the
+    /// farthest word.
+    fn conjunct_sels(&self, conjunction_sel: &LLSelection, forwards: bool) -> Vec<LLSelection> {
+        let token = x::all((x::attr::<TextTag>(), x::token_text()));
+        let mut conjunct_sels = Vec::new();
+        let mut selection = conjunction_sel.clone();
+        let mut is_first_token = true;
+
+        loop {
+            let (next_sel, (tag, text)) = match if forwards {
+                selection.match_first_forwards(&token)
+            } else {
+                selection.match_first_backwards(&token)
+            } {
+                Some(found) => found,
+                None => break,
+            };
+
+            match tag {
+                TextTag::SPACE => {}
+                TextTag::PUNC if is_first_token && !forwards && text == "," => {}
+                TextTag::WORD | TextTag::NATN
+                    if !self.conjunctions.contains(&text.to_lowercase()) =>
+                {
+                    conjunct_sels.push(next_sel.clone());
+                }
+                _ => break,
+            }
+
+            if *tag != TextTag::SPACE {
+                is_first_token = false;
+            }
+            selection = next_sel;
+        }
+
+        conjunct_sels
+    }
+}
+
+impl Default for CoordinationResolver {
+    /// English "and", "or", "but" and "nor".
+    fn default() -> Self {
+        CoordinationResolver::new(&["and", "or", "but", "nor"])
+    }
+}
+
+impl Resolver for CoordinationResolver {
+    type Attr = Coordination;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .into_iter()
+            .filter(|(_, (_, text))| self.conjunctions.contains(&text.to_lowercase()))
+            .filter_map(|(conjunction_sel, _)| {
+                let left_sels = self.conjunct_sels(&conjunction_sel, false);
+                let right_sels = self.conjunct_sels(&conjunction_sel, true);
+                let word_count = left_sels.len().min(right_sels.len());
+                if word_count == 0 {
+                    return None;
+                }
+
+                let left_start_idx = left_sels[word_count - 1].token_range().0;
+                let left_end_idx = left_sels[0].token_range().0;
+                let right_start_idx = right_sels[0].token_range().1;
+                let right_end_idx = right_sels[word_count - 1].token_range().1;
+
+                // stretch the left conjunct selection up to the end of the right conjunct
+                let mut coordination_sel = left_sels[word_count - 1].clone();
+                while coordination_sel.token_range().1 < right_end_idx {
+                    coordination_sel = coordination_sel
+                        .match_first_forwards(&x::attr::<TextTag>())
+                        .expect("right conjunct tokens were matched already")
+                        .0;
+                }
+
+                Some(coordination_sel.finish_with_attr(Coordination {
+                    left_range: (left_start_idx, left_end_idx),
+                    right_range: (right_start_idx, right_end_idx),
+                }))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string(
+        "I like red apples and green pears, tea or coffee. Red, green, and blue",
+    )
+    .run(&CoordinationResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Coordination>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    I     like     red     apples     and     green     pears  ,     tea     or     coffee  .     Red  ,     green  ,     and     blue
+                   ╰────────────────────────────────────────╯Coordination { left_range: (4, 6), right_range: (10, 12) }
+                                                                     ╰───────────────────╯Coordination { left_range: (15, 15), right_range: (19, 19) }
+                                                                                                             ╰───────────────────────╯Coordination { left_range: (25, 25), right_range: (30, 30) }
+    "###);
+}
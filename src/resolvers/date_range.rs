@@ -0,0 +1,255 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// A calendar date, as far as it was written (or shared from the other end
+/// of a [DateRange]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Date {
+    pub year: Option<u32>,
+    /// From 1 for January
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+}
+
+/// A range of dates like `Jan 3–7, 2024` or `2020–2023`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DateRange {
+    pub start: Date,
+    pub end: Date,
+}
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+const DASHES: [char; 3] = ['-', '–', '—'];
+
+/// Recognizes ranges of dates written with a dash (`-`, `–` or `—`), and
+/// shares the month and year written on one end with the other:
+///  * `Jan 3–7, 2024` is January 3rd to January 7th, 2024
+///  * `Jan 30–Feb 2, 2024`, and `Dec 28–Jan 3, 2025` which starts in 2024
+///  * `March–May 2023`
+///  * `2020–2023`
+///
+/// Both ends need the same parts, except for the day only form `Jan 3–7`.
+#[derive(Default)]
+pub struct DateRangeResolver(());
+
+impl DateRangeResolver {
+    /// Capitalized month names, or their abbreviations like `Jan` and `Sept`
+    fn month(text: &str) -> Option<u8> {
+        if !text.starts_with(char::is_uppercase) || text.len() < 3 {
+            return None;
+        }
+
+        let lower = text.to_lowercase();
+        let position = MONTHS.iter().position(|month| month.starts_with(&lower))?;
+        Some(position as u8 + 1)
+    }
+
+    /// `selection` extended over the whitespace following it, if any
+    fn skip_space(selection: LLSelection) -> LLSelection {
+        selection
+            .match_first_forwards(&x::whitespace())
+            .map(|(space_sel, _)| space_sel)
+            .unwrap_or(selection)
+    }
+
+    /// The number following `selection`, if it has `min_digits` to `max_digits` digits
+    fn number(
+        selection: &LLSelection,
+        min_digits: usize,
+        max_digits: usize,
+    ) -> Option<(LLSelection, u32)> {
+        let (number_sel, (_, text)) = selection
+            .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))?;
+
+        if (min_digits..=max_digits).contains(&text.len()) {
+            Some((number_sel, text.parse().ok()?))
+        } else {
+            None
+        }
+    }
+
+    /// The year following `selection`, like `2024` or `, 2024` after a day.
+    fn with_year(selection: LLSelection, mut date: Date) -> (LLSelection, Date) {
+        let before_year = match date.day {
+            Some(_) => selection
+                .match_first_forwards(&x::token_has_any(&[',']))
+                .map(|(comma_sel, _)| comma_sel)
+                .unwrap_or_else(|| selection.clone()),
+            None => selection.clone(),
+        };
+
+        match Self::number(&Self::skip_space(before_year), 4, 4) {
+            Some((year_sel, year)) => {
+                date.year = Some(year);
+                (year_sel, date)
+            }
+            None => (selection, date),
+        }
+    }
+
+    /// A date starting with the month word `month_sel`: `Jan`, `Jan 3`,
+    /// `Jan 3, 2024` or `Jan 2024`
+    fn month_date(month_sel: LLSelection, month: u8) -> (LLSelection, Date) {
+        let date = Date {
+            year: None,
+            month: Some(month),
+            day: None,
+        };
+
+        match Self::number(&Self::skip_space(month_sel.clone()), 1, 2) {
+            Some((day_sel, day)) if (1..=31).contains(&day) => Self::with_year(
+                day_sel,
+                Date {
+                    day: Some(day as u8),
+                    ..date
+                },
+            ),
+            _ => Self::with_year(month_sel, date),
+        }
+    }
+
+    /// The date covered by `date_sel`, where the `text` token is its first
+    fn date(
+        date_sel: LLSelection,
+        tag: &TextTag,
+        text: &str,
+        is_end: bool,
+    ) -> Option<(LLSelection, Date)> {
+        match tag {
+            TextTag::WORD => Some(Self::month_date(date_sel, Self::month(text)?)),
+            TextTag::NATN if text.len() == 4 => Some((
+                date_sel,
+                Date {
+                    year: Some(text.parse().ok()?),
+                    month: None,
+                    day: None,
+                },
+            )),
+            // only the end of `Jan 3–7` has a lone day
+            TextTag::NATN if is_end && text.len() <= 2 => {
+                let day: u8 = text.parse().ok()?;
+                if !(1..=31).contains(&day) {
+                    return None;
+                }
+
+                Some(Self::with_year(
+                    date_sel,
+                    Date {
+                        year: None,
+                        month: None,
+                        day: Some(day),
+                    },
+                ))
+            }
+            _ => None,
+        }
+    }
+
+    /// Shares the month and year between both ends, if they form a range
+    fn distribute(mut start: Date, mut end: Date) -> Option<DateRange> {
+        match (start.month, start.day, end.month, end.day) {
+            // `Jan 3–7`
+            (Some(_), Some(_), None, Some(_)) => end.month = start.month,
+            // `2020–2023`, `March–May`, `Jan 30–Feb 2`
+            (None, None, None, None)
+            | (Some(_), None, Some(_), None)
+            | (Some(_), Some(_), Some(_), Some(_)) => {}
+            _ => return None,
+        }
+
+        // `Dec 28–Jan 3, 2025` wraps around the year
+        let wraps = start.month > end.month;
+        match (start.year, end.year) {
+            (None, Some(year)) => {
+                start.year = Some(if wraps { year.checked_sub(1)? } else { year })
+            }
+            (Some(year), None) => end.year = Some(if wraps { year + 1 } else { year }),
+            _ => {}
+        }
+
+        if (start.year, start.month, start.day) < (end.year, end.month, end.day) {
+            Some(DateRange { start, end })
+        } else {
+            None
+        }
+    }
+
+    fn match_range(
+        start_sel: &LLSelection,
+        tag: &TextTag,
+        text: &str,
+    ) -> Option<(LLSelection, DateRange)> {
+        let (start_date_sel, start) = Self::date(start_sel.clone(), tag, text, false)?;
+
+        let (dash_sel, _) =
+            Self::skip_space(start_date_sel).match_first_forwards(&x::token_has_any(&DASHES))?;
+        let dash_sel = Self::skip_space(dash_sel);
+        let (end_sel, (end_tag, end_text)) =
+            dash_sel.match_first_forwards(&x::all((x::attr::<TextTag>(), x::token_text())))?;
+        let (range_sel, end) = Self::date(end_sel, end_tag, end_text, true)?;
+
+        Some((range_sel, Self::distribute(start, end)?))
+    }
+}
+
+impl Resolver for DateRangeResolver {
+    type Attr = DateRange;
+
+    fn go(&self, mut search_range_sel: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let mut attrs = Vec::new();
+
+        while let Some((start_sel, (tag, text))) =
+            search_range_sel.find_first_by(&x::all((x::attr::<TextTag>(), x::token_text())))
+        {
+            let consumed_sel = match Self::match_range(&start_sel, tag, text) {
+                Some((range_sel, date_range)) => {
+                    attrs.push(range_sel.finish_with_attr(date_range));
+                    range_sel
+                }
+                None => start_sel,
+            };
+
+            if let [_, Some(right_sel)] = search_range_sel.split_with(&consumed_sel) {
+                search_range_sel = right_sel;
+            } else {
+                break;
+            }
+        }
+
+        attrs
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string(
+        "Open Jan 3–7, 2024 and Dec 28 - Jan 3, 2025, closed 2020–2023, from March–May 2023, not pages 5-7",
+    )
+    .run(&DateRangeResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<DateRange>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    Open     Jan     3  –  7  ,     2024     and     Dec     28     -     Jan     3  ,     2025  ,     closed     2020  –  2023  ,     from     March  –  May     2023  ,     not     pages     5  -  7
+             ╰─────────────────────────╯DateRange { start: Date { year: Some(2024), month: Some(1), day: Some(3) }, end: Date { year: Some(2024), month: Some(1), day: Some(7) } }
+                                                     ╰────────────────────────────────────────╯DateRange { start: Date { year: Some(2024), month: Some(12), day: Some(28) }, end: Date { year: Some(2025), month: Some(1), day: Some(3) } }
+                                                                                                                  ╰───────────╯DateRange { start: Date { year: Some(2020), month: None, day: None }, end: Date { year: Some(2023), month: None, day: None } }
+                                                                                                                                                ╰────────────────────╯DateRange { start: Date { year: Some(2023), month: Some(3), day: None }, end: Date { year: Some(2023), month: Some(5), day: None } }
+    "###);
+}
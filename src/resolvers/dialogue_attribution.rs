@@ -0,0 +1,219 @@
+use crate::{x, LLCursorAssignment, LLSelection, Quoted, Resolver, TextTag};
+
+/// A [Quoted] span and who said it, like `"Hello," she said`.
+///
+/// Assigned over both the quote and its attribution clause.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attribution {
+    /// (start, end) token indexes of the quote, quote marks included
+    pub quote_range: (usize, usize),
+    /// (start, end) token indexes of the speaker
+    pub speaker_range: (usize, usize),
+}
+
+/// Pronouns accepted as speakers, other speakers have to be capitalized words
+const SPEAKER_PRONOUNS: &[&str] = &["i", "you", "he", "she", "it", "we", "they"];
+
+/// Capitalized words often opening a sentence, as in `Then Mary said`
+const NON_SPEAKER_WORDS: &[&str] = &["then", "and", "but", "so", "now", "later", "finally"];
+
+/// Longest speaker, as in `John Ronald Tolkien said`
+const MAX_SPEAKER_WORDS: usize = 3;
+
+/// Links [Quoted] spans to an adjacent "X said" clause, run [crate::QuotedResolver] first.
+///
+/// Handles attributions after the quote (`"Hello," she said`, `"Hello", said
+/// John`) and before it (`John said, "Hello"`, `She asked: "Why?"`). When a
+/// quote has both, the one after it wins.
+pub struct DialogueAttributionResolver {
+    /// Lowercased speech verbs
+    verbs: Vec<String>,
+}
+
+impl DialogueAttributionResolver {
+    pub fn new(verbs: &[&str]) -> Self {
+        DialogueAttributionResolver {
+            verbs: verbs.iter().map(|verb| verb.to_lowercase()).collect(),
+        }
+    }
+
+    fn is_verb(&self, text: &str) -> bool {
+        self.verbs.contains(&text.to_lowercase())
+    }
+
+    fn is_speaker_word(&self, text: &str) -> bool {
+        let lowercase = text.to_lowercase();
+        !self.is_verb(text)
+            && !NON_SPEAKER_WORDS.contains(&lowercase.as_str())
+            && (text.starts_with(char::is_uppercase)
+                || SPEAKER_PRONOUNS.contains(&lowercase.as_str()))
+    }
+
+    /// Extends `selection` with the speech verb right after it (or before it when going backwards).
+    fn match_verb(&self, selection: &LLSelection, forwards: bool) -> Option<LLSelection> {
+        let word = x::all((x::attr_eq(&TextTag::WORD), x::token_text()));
+        let (verb_sel, (_, text)) = if forwards {
+            selection.match_first_forwards(&word)
+        } else {
+            selection.match_first_backwards(&word)
+        }?;
+
+        if self.is_verb(text) {
+            Some(verb_sel)
+        } else {
+            None
+        }
+    }
+
+    /// Extends `selection` with the speaker words right after it (or before
+    /// it when going backwards), along with the speaker range.
+    fn match_speaker(
+        &self,
+        selection: &LLSelection,
+        forwards: bool,
+    ) -> Option<(LLSelection, (usize, usize))> {
+        let word = x::all((x::attr_eq(&TextTag::WORD), x::token_text()));
+        let mut speaker: Option<(LLSelection, usize)> = None;
+
+        for _ in 0..MAX_SPEAKER_WORDS {
+            let from_sel = match &speaker {
+                None => selection.clone(),
+                Some((speaker_sel, _)) => match if forwards {
+                    speaker_sel.match_first_forwards(&x::whitespace())
+                } else {
+                    speaker_sel.match_first_backwards(&x::whitespace())
+                } {
+                    Some((space_sel, _)) => space_sel,
+                    None => break,
+                },
+            };
+
+            match if forwards {
+                from_sel.match_first_forwards(&word)
+            } else {
+                from_sel.match_first_backwards(&word)
+            } {
+                Some((word_sel, (_, text))) if self.is_speaker_word(text) => {
+                    let first_word_idx = match &speaker {
+                        Some((_, first_word_idx)) => *first_word_idx,
+                        None if forwards => word_sel.token_range().1,
+                        None => word_sel.token_range().0,
+                    };
+                    speaker = Some((word_sel, first_word_idx));
+                }
+                _ => break,
+            }
+        }
+
+        speaker.map(|(speaker_sel, first_word_idx)| {
+            let speaker_range = if forwards {
+                (first_word_idx, speaker_sel.token_range().1)
+            } else {
+                (speaker_sel.token_range().0, first_word_idx)
+            };
+            (speaker_sel, speaker_range)
+        })
+    }
+
+    /// `"Hello," she said` or `"Hello," said John`
+    fn match_after(&self, quote_sel: &LLSelection) -> Option<(LLSelection, (usize, usize))> {
+        let selection = quote_sel
+            .match_first_forwards(&x::token_has_any(&[',']))
+            .map(|(comma_sel, _)| comma_sel)
+            .unwrap_or_else(|| quote_sel.clone());
+        let (selection, _) = selection.match_first_forwards(&x::whitespace())?;
+
+        if let Some(verb_sel) = self.match_verb(&selection, true) {
+            let (space_sel, _) = verb_sel.match_first_forwards(&x::whitespace())?;
+            self.match_speaker(&space_sel, true)
+        } else {
+            let (speaker_sel, speaker_range) = self.match_speaker(&selection, true)?;
+            let (space_sel, _) = speaker_sel.match_first_forwards(&x::whitespace())?;
+            let verb_sel = self.match_verb(&space_sel, true)?;
+            Some((verb_sel, speaker_range))
+        }
+    }
+
+    /// `John said, "Hello"`
+    fn match_before(&self, quote_sel: &LLSelection) -> Option<(LLSelection, (usize, usize))> {
+        let mut selection = quote_sel.clone();
+        if let Some((space_sel, _)) = selection.match_first_backwards(&x::whitespace()) {
+            selection = space_sel;
+        }
+        if let Some((separator_sel, _)) =
+            selection.match_first_backwards(&x::token_has_any(&[',', ':']))
+        {
+            selection = separator_sel;
+        }
+
+        let verb_sel = self.match_verb(&selection, false)?;
+        let (space_sel, _) = verb_sel.match_first_backwards(&x::whitespace())?;
+        self.match_speaker(&space_sel, false)
+    }
+}
+
+impl Default for DialogueAttributionResolver {
+    /// Common English speech verbs, like "said", "asked" or "replied".
+    fn default() -> Self {
+        DialogueAttributionResolver::new(&[
+            "said",
+            "says",
+            "asked",
+            "asks",
+            "replied",
+            "replies",
+            "answered",
+            "answers",
+            "added",
+            "adds",
+            "shouted",
+            "shouts",
+            "whispered",
+            "whispers",
+            "cried",
+            "cries",
+        ])
+    }
+}
+
+impl Resolver for DialogueAttributionResolver {
+    type Attr = Attribution;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::attr::<Quoted>())
+            .into_iter()
+            .filter_map(|(quote_sel, _)| {
+                let (attribution_sel, speaker_range) = self
+                    .match_after(&quote_sel)
+                    .or_else(|| self.match_before(&quote_sel))?;
+
+                Some(attribution_sel.finish_with_attr(Attribution {
+                    quote_range: quote_sel.token_range(),
+                    speaker_range,
+                }))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay, QuotedResolver};
+
+    let ll_line = create_line_from_string(
+        r#""Hello," she said. "Hi", said John Doe. Then Mary asked: "Why?" "Because.""#,
+    )
+    .run(&QuotedResolver::default())
+    .run(&DialogueAttributionResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Attribution>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    "  Hello  ,  "     she     said  .     "  Hi  "  ,     said     John     Doe  .     Then     Mary     asked  :     "  Why  ?  "     "  Because  .  "
+    ╰─────────────────────────────╯Attribution { quote_range: (0, 3), speaker_range: (5, 5) }
+                                           ╰───────────────────────────────────╯Attribution { quote_range: (10, 12), speaker_range: (17, 19) }
+                                                                                                 ╰────────────────────────────────╯Attribution { quote_range: (29, 32), speaker_range: (24, 24) }
+    "###);
+}
@@ -0,0 +1,155 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// Measurements along several axes, like `4x8 feet` or `1920x1080`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dimensions {
+    pub values: Vec<f64>,
+    /// Unit applying to all the values, as written
+    pub unit: Option<String>,
+}
+
+/// Matches numbers joined by `x` or `×`, with or without spaces: `4x8`,
+/// `3 x 5 inches`, `2.5×4`, `2x4x8`.
+///
+/// A known unit word right after the last number is taken as the unit of all
+/// the values.
+pub struct DimensionsResolver {
+    /// Lowercased unit words
+    units: Vec<String>,
+}
+
+impl DimensionsResolver {
+    pub fn new(units: &[&str]) -> Self {
+        DimensionsResolver {
+            units: units.iter().map(|unit| unit.to_lowercase()).collect(),
+        }
+    }
+
+    /// Extends `integer_sel` with the decimals following it, as in `2.5`
+    fn match_decimals(integer_sel: LLSelection, integer: &str) -> (LLSelection, f64) {
+        if let Some((decimals_sel, decimals)) = integer_sel
+            .match_first_forwards(&x::token_has_any(&['.']))
+            .and_then(|(point_sel, _)| {
+                let (decimals_sel, (_, decimals)) = point_sel
+                    .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))?;
+                Some((decimals_sel, decimals.to_string()))
+            })
+        {
+            let value = format!("{}.{}", integer, decimals)
+                .parse()
+                .expect("digits are a valid float");
+            return (decimals_sel, value);
+        }
+
+        (
+            integer_sel,
+            integer.parse().expect("digits are a valid float"),
+        )
+    }
+
+    /// Extends `selection` with ` x 5`
+    fn match_next_value(selection: &LLSelection) -> Option<(LLSelection, f64)> {
+        let skip_whitespace =
+            |selection: LLSelection| match selection.match_first_forwards(&x::whitespace()) {
+                Some((space_sel, _)) => space_sel,
+                None => selection,
+            };
+
+        let selection = skip_whitespace(selection.clone());
+        let (separator_sel, (tag, text)) =
+            selection.match_first_forwards(&x::all((x::attr::<TextTag>(), x::token_text())))?;
+        let is_separator = match tag {
+            TextTag::WORD => text == "x" || text == "X",
+            _ => text == "×",
+        };
+        if !is_separator {
+            return None;
+        }
+
+        let before_integer_sel = skip_whitespace(separator_sel);
+        let (integer_sel, (_, integer)) = before_integer_sel
+            .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))?;
+        Some(Self::match_decimals(integer_sel, integer))
+    }
+}
+
+impl Default for DimensionsResolver {
+    /// Common length units, like "cm", "inches", "feet" or "px".
+    fn default() -> Self {
+        DimensionsResolver::new(&[
+            "mm", "cm", "m", "km", "in", "inch", "inches", "ft", "foot", "feet", "yd", "yard",
+            "yards", "px", "pixels",
+        ])
+    }
+}
+
+impl Resolver for DimensionsResolver {
+    type Attr = Dimensions;
+
+    fn go(&self, mut search_range_sel: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let mut attrs = Vec::new();
+
+        while let Some((integer_sel, (_, integer))) =
+            search_range_sel.find_first_by(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))
+        {
+            let (mut selection, first_value) = Self::match_decimals(integer_sel, integer);
+            let mut values = vec![first_value];
+
+            while let Some((value_sel, value)) = Self::match_next_value(&selection) {
+                values.push(value);
+                selection = value_sel;
+            }
+
+            if values.len() > 1 {
+                let unit = selection
+                    .match_first_forwards(&x::whitespace())
+                    .and_then(|(space_sel, _)| {
+                        let (unit_sel, (_, text)) = space_sel.match_first_forwards(&x::all((
+                            x::attr_eq(&TextTag::WORD),
+                            x::token_text(),
+                        )))?;
+                        Some((unit_sel, text.to_string()))
+                    })
+                    .filter(|(_, text)| self.units.contains(&text.to_lowercase()));
+
+                let (dimensions_sel, unit) = match unit {
+                    Some((unit_sel, text)) => (unit_sel, Some(text)),
+                    None => (selection, None),
+                };
+
+                attrs.push(dimensions_sel.finish_with_attr(Dimensions { values, unit }));
+                selection = dimensions_sel;
+            }
+
+            if let [_, Some(right_sel)] = search_range_sel.split_with(&selection) {
+                search_range_sel = right_sel;
+            } else {
+                break;
+            }
+        }
+
+        attrs
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string(
+        "A 4x8 feet plank, 3 x 5 inches cards, a 2.5×4 tile, 2x4x8 bricks and 1920x1080 at 60 fps",
+    )
+    .run(&DimensionsResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Dimensions>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    A     4  x  8     feet     plank  ,     3     x     5     inches     cards  ,     a     2  .  5  ×  4     tile  ,     2  x  4  x  8     bricks     and     1920  x  1080     at     60     fps
+          ╰──────────────╯Dimensions { values: [4.0, 8.0], unit: Some("feet") }
+                                            ╰──────────────────────╯Dimensions { values: [3.0, 5.0], unit: Some("inches") }
+                                                                                            ╰───────────╯Dimensions { values: [2.5, 4.0], unit: None }
+                                                                                                                          ╰───────────╯Dimensions { values: [2.0, 4.0, 8.0], unit: None }
+                                                                                                                                                               ╰───────────╯Dimensions { values: [1920.0, 1080.0], unit: None }
+    "###);
+}
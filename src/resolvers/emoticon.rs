@@ -0,0 +1,162 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// A text emoticon like `:-)` or `<3`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Emoticon {
+    /// From -1.0 (negative) to 1.0 (positive)
+    pub sentiment: f32,
+    pub kind: String,
+}
+
+/// Matches emoticons from a table, across the tokens they are split into:
+/// `:-(` is the three tokens `:`, `-` and `(`.
+///
+/// Emoticons glued to a word or a number on either side are left alone, so
+/// the `:D` of `a:Do` doesn't match. The longest pattern wins.
+pub struct EmoticonResolver {
+    /// (pattern, kind, sentiment)
+    patterns: Vec<(String, String, f32)>,
+    /// Longest pattern, in chars
+    max_len: usize,
+}
+
+impl EmoticonResolver {
+    /// `patterns` are (pattern, kind, sentiment) entries, like `(":)", "smile", 0.5)`.
+    pub fn new(patterns: &[(&str, &str, f32)]) -> Self {
+        EmoticonResolver {
+            patterns: patterns
+                .iter()
+                .map(|(pattern, kind, sentiment)| {
+                    (pattern.to_string(), kind.to_string(), *sentiment)
+                })
+                .collect(),
+            max_len: patterns
+                .iter()
+                .map(|(pattern, _, _)| pattern.chars().count())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    fn lookup(&self, text: &str) -> Option<Emoticon> {
+        self.patterns
+            .iter()
+            .find(|(pattern, _, _)| pattern == text)
+            .map(|(_, kind, sentiment)| Emoticon {
+                sentiment: *sentiment,
+                kind: kind.clone(),
+            })
+    }
+
+    /// The longest emoticon starting at `start_sel`
+    fn match_emoticon(
+        &self,
+        start_sel: &LLSelection,
+        start_text: &str,
+    ) -> Option<(LLSelection, Emoticon)> {
+        let is_glued = |tag: Option<(LLSelection, &TextTag)>| {
+            matches!(tag, Some((_, TextTag::WORD)) | Some((_, TextTag::NATN)))
+        };
+        if is_glued(start_sel.match_first_backwards(&x::attr::<TextTag>())) {
+            return None;
+        }
+
+        let mut selection = start_sel.clone();
+        let mut text = start_text.to_string();
+        let mut longest = None;
+
+        loop {
+            if let Some(emoticon) = self.lookup(&text) {
+                if !is_glued(selection.match_first_forwards(&x::attr::<TextTag>())) {
+                    longest = Some((selection.clone(), emoticon));
+                }
+            }
+
+            match selection.match_first_forwards(&x::all((x::attr::<TextTag>(), x::token_text()))) {
+                Some((next_sel, (tag, next_text)))
+                    if *tag != TextTag::SPACE
+                        && text.chars().count() + next_text.chars().count() <= self.max_len =>
+                {
+                    text.push_str(next_text);
+                    selection = next_sel;
+                }
+                _ => break,
+            }
+        }
+
+        longest
+    }
+}
+
+impl Default for EmoticonResolver {
+    /// Common western emoticons, like ":)", ":-(", ";)" or "<3".
+    fn default() -> Self {
+        EmoticonResolver::new(&[
+            (":)", "smile", 0.5),
+            (":-)", "smile", 0.5),
+            ("=)", "smile", 0.5),
+            (":D", "laugh", 0.8),
+            (":-D", "laugh", 0.8),
+            ("xD", "laugh", 0.8),
+            ("XD", "laugh", 0.8),
+            (";)", "wink", 0.4),
+            (";-)", "wink", 0.4),
+            (":P", "tongue", 0.3),
+            (":-P", "tongue", 0.3),
+            (":(", "frown", -0.5),
+            (":-(", "frown", -0.5),
+            (":'(", "cry", -0.8),
+            (":/", "skeptical", -0.2),
+            (":-/", "skeptical", -0.2),
+            (":|", "neutral", 0.0),
+            (":-|", "neutral", 0.0),
+            ("<3", "heart", 0.8),
+            ("</3", "broken heart", -0.7),
+        ])
+    }
+}
+
+impl Resolver for EmoticonResolver {
+    type Attr = Emoticon;
+
+    fn go(&self, mut search_range_sel: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let mut attrs = Vec::new();
+
+        while let Some((start_sel, text)) = search_range_sel.find_first_by(&x::token_text()) {
+            let consumed_sel = match self.match_emoticon(&start_sel, text) {
+                Some((emoticon_sel, emoticon)) => {
+                    attrs.push(emoticon_sel.finish_with_attr(emoticon));
+                    emoticon_sel
+                }
+                None => start_sel,
+            };
+
+            if let [_, Some(right_sel)] = search_range_sel.split_with(&consumed_sel) {
+                search_range_sel = right_sel;
+            } else {
+                break;
+            }
+        }
+
+        attrs
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string("Great :-) but sad :'( <3 xD, not http://a or 12:30")
+        .run(&EmoticonResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Emoticon>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    Great     :  -  )     but     sad     :  '  (     <  3     xD  ,     not     http  :  /  /  a     or     12  :  30
+              ╰─────╯Emoticon { sentiment: 0.5, kind: "smile" }
+                                          ╰─────╯Emoticon { sentiment: -0.8, kind: "cry" }
+                                                      ╰──╯Emoticon { sentiment: 0.8, kind: "heart" }
+                                                               ╰╯Emoticon { sentiment: 0.8, kind: "laugh" }
+    "###);
+}
@@ -0,0 +1,236 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// An IP address like `192.168.0.1`, with the port of `192.168.0.1:8080`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IpAddr {
+    pub addr: std::net::IpAddr,
+    pub port: Option<u16>,
+}
+
+/// Recognizes dotted-quad IPv4 addresses with an optional `:port`.
+///
+/// Octets out of the 0-255 range, and dotted runs of more than four numbers
+/// (`1.2.3.4.5`) are left alone.
+///
+/// IPv6 addresses are opt-in, since their hex groups are split into words,
+/// numbers and colons by the tokenizer. They are matched bare (`fe80::1`), or
+/// in brackets when they have a port (`[::1]:8080`).
+pub struct IpAddressResolver {
+    ipv6: bool,
+}
+
+impl IpAddressResolver {
+    pub fn new(ipv6: bool) -> Self {
+        IpAddressResolver { ipv6 }
+    }
+
+    /// `:8080`, right after `selection`
+    fn match_port(selection: &LLSelection) -> Option<(LLSelection, u16)> {
+        let (colon_sel, _) = selection.match_first_forwards(&x::token_has_any(&[':']))?;
+        let (port_sel, (_, text)) = colon_sel
+            .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))?;
+
+        Some((port_sel, text.parse().ok()?))
+    }
+
+    /// Four dotted numbers starting at `start_sel`
+    fn match_ipv4(start_sel: &LLSelection, first_octet: &str) -> Option<(LLSelection, Ipv4Addr)> {
+        let natn = x::all((x::attr_eq(&TextTag::NATN), x::token_text()));
+        let dot = x::token_has_any(&['.']);
+
+        // not the end of a longer dotted run
+        if let Some((dot_sel, _)) = start_sel.match_first_backwards(&dot) {
+            if dot_sel.match_first_backwards(&natn).is_some() {
+                return None;
+            }
+        }
+
+        let mut address = String::from(first_octet);
+        let mut selection = start_sel.clone();
+        for _ in 0..3 {
+            let (dot_sel, _) = selection.match_first_forwards(&dot)?;
+            let (octet_sel, (_, text)) = dot_sel.match_first_forwards(&natn)?;
+            address.push('.');
+            address.push_str(text);
+            selection = octet_sel;
+        }
+
+        // not the start of a longer dotted run
+        if let Some((dot_sel, _)) = selection.match_first_forwards(&dot) {
+            if dot_sel.match_first_forwards(&natn).is_some() {
+                return None;
+            }
+        }
+
+        Some((selection, address.parse().ok()?))
+    }
+
+    /// The longest run of hex and colon tokens starting at `start_sel` which forms an address
+    fn match_ipv6(start_sel: &LLSelection, first_text: &str) -> Option<(LLSelection, Ipv6Addr)> {
+        let is_hex_or_colon =
+            |text: &str| text.chars().all(|ch| ch == ':' || ch.is_ascii_hexdigit());
+        if !is_hex_or_colon(first_text) {
+            return None;
+        }
+
+        // not in the middle of a word, like `key::value`
+        if let Some((_, tag)) = start_sel.match_first_backwards(&x::attr::<TextTag>()) {
+            if matches!(tag, TextTag::WORD | TextTag::NATN | TextTag::SYMB) {
+                return None;
+            }
+        }
+
+        let mut candidates = vec![(start_sel.clone(), first_text.to_string())];
+        loop {
+            let (last_sel, last_text) = candidates.last().expect("starts with one candidate");
+            match last_sel.match_first_forwards(&x::token_text()) {
+                Some((next_sel, text)) if is_hex_or_colon(text) => {
+                    let candidate_text = format!("{}{}", last_text, text);
+                    candidates.push((next_sel, candidate_text));
+                }
+                _ => break,
+            }
+        }
+
+        candidates
+            .into_iter()
+            .rev()
+            // `::` alone is rather a separator
+            .filter(|(_, text)| text.contains(|ch: char| ch.is_ascii_hexdigit()))
+            .find_map(|(selection, text)| Some((selection, text.parse().ok()?)))
+    }
+
+    /// `[::1]:8080`, starting at the opening bracket `start_sel`
+    fn match_bracketed_ipv6(start_sel: &LLSelection) -> Option<(LLSelection, IpAddr)> {
+        let (first_sel, first_text) = start_sel.match_first_forwards(&x::token_text())?;
+        let (address_sel, addr) = Self::match_ipv6(&first_sel, first_text)?;
+        let (closing_sel, _) = address_sel.match_first_forwards(&x::token_has_any(&[']']))?;
+
+        Some(match Self::match_port(&closing_sel) {
+            Some((port_sel, port)) => (
+                port_sel,
+                IpAddr {
+                    addr: addr.into(),
+                    port: Some(port),
+                },
+            ),
+            None => (
+                closing_sel,
+                IpAddr {
+                    addr: addr.into(),
+                    port: None,
+                },
+            ),
+        })
+    }
+
+    fn match_address(
+        &self,
+        start_sel: &LLSelection,
+        tag: &TextTag,
+        text: &str,
+    ) -> Option<(LLSelection, IpAddr)> {
+        if *tag == TextTag::NATN {
+            if let Some((address_sel, addr)) = Self::match_ipv4(start_sel, text) {
+                return Some(match Self::match_port(&address_sel) {
+                    Some((port_sel, port)) => (
+                        port_sel,
+                        IpAddr {
+                            addr: addr.into(),
+                            port: Some(port),
+                        },
+                    ),
+                    None => (
+                        address_sel,
+                        IpAddr {
+                            addr: addr.into(),
+                            port: None,
+                        },
+                    ),
+                });
+            }
+        }
+
+        if !self.ipv6 {
+            return None;
+        }
+
+        if text == "[" {
+            return Self::match_bracketed_ipv6(start_sel);
+        }
+
+        let (address_sel, addr) = Self::match_ipv6(start_sel, text)?;
+        Some((
+            address_sel,
+            IpAddr {
+                addr: addr.into(),
+                port: None,
+            },
+        ))
+    }
+}
+
+impl Default for IpAddressResolver {
+    /// IPv4 only.
+    fn default() -> Self {
+        IpAddressResolver::new(false)
+    }
+}
+
+impl Resolver for IpAddressResolver {
+    type Attr = IpAddr;
+
+    fn go(&self, mut search_range_sel: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let mut attrs = Vec::new();
+
+        while let Some((start_sel, (tag, text))) =
+            search_range_sel.find_first_by(&x::all((x::attr::<TextTag>(), x::token_text())))
+        {
+            let consumed_sel = match self.match_address(&start_sel, tag, text) {
+                Some((address_sel, ip_addr)) => {
+                    attrs.push(address_sel.finish_with_attr(ip_addr));
+                    address_sel
+                }
+                None => start_sel,
+            };
+
+            if let [_, Some(right_sel)] = search_range_sel.split_with(&consumed_sel) {
+                search_range_sel = right_sel;
+            } else {
+                break;
+            }
+        }
+
+        attrs
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let line = "from 10.0.0.1:8080 to 192.168.1.255, not 256.1.1.1 nor 1.2.3.4.5 or fe80::1";
+
+    let ll_line = create_line_from_string(line).run(&IpAddressResolver::default());
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<IpAddr>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    from     10  .  0  .  0  .  1  :  8080     to     192  .  168  .  1  .  255  ,     not     256  .  1  .  1  .  1     nor     1  .  2  .  3  .  4  .  5     or     fe80  :  :  1
+             ╰───────────────────────────╯IpAddr { addr: 10.0.0.1, port: Some(8080) }
+                                                      ╰───────────────────────╯IpAddr { addr: 192.168.1.255, port: None }
+    "###);
+
+    let ll_line = create_line_from_string("ping fe80::1 and [2001:db8::ff00:42]:443, key::value")
+        .run(&IpAddressResolver::new(true));
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<IpAddr>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    ping     fe80  :  :  1     and     [  2001  :  db8  :  :  ff00  :  42  ]  :  443  ,     key  :  :  value
+             ╰───────────╯IpAddr { addr: fe80::1, port: None }
+                                       ╰───────────────────────────────────────────╯IpAddr { addr: 2001:db8::ff00:42, port: Some(443) }
+    "###);
+}
@@ -0,0 +1,118 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+use super::Quoted;
+
+/// Inline `key=value` or `key: value` pair.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyValue {
+    /// (start, end) token indexes of the key
+    pub key_range: (usize, usize),
+    /// (start, end) token indexes of the value
+    pub value_range: (usize, usize),
+}
+
+/// Matches a [TextTag::WORD] key, directly followed by a separator, then a value.
+/// A `:` directly followed by `/` is part of a URL like `http://example.com`,
+/// not a separator.
+///
+/// The value is either a [Quoted] span (run [super::QuotedResolver] first) or
+/// a run of tokens stopping at whitespace. A trailing `,` or `;` is left out
+/// of the value so `a=1, b=2` yields two pairs.
+pub struct KeyValueResolver {
+    separators: Vec<char>,
+}
+
+impl KeyValueResolver {
+    pub fn new(separators: Vec<char>) -> Self {
+        KeyValueResolver { separators }
+    }
+}
+
+impl Default for KeyValueResolver {
+    /// `=` and `:` separators.
+    fn default() -> Self {
+        KeyValueResolver::new(vec!['=', ':'])
+    }
+}
+
+impl Resolver for KeyValueResolver {
+    type Attr = KeyValue;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::attr_eq(&TextTag::WORD))
+            .into_iter()
+            .filter_map(|(key_sel, _)| {
+                let (separator_sel, separator) =
+                    key_sel.match_first_forwards(&x::token_has_any(&self.separators))?;
+                if *separator == ':'
+                    && separator_sel
+                        .match_first_forwards(&x::token_has_any(&['/']))
+                        .is_some()
+                {
+                    return None;
+                }
+                let before_value_sel = match separator_sel.match_first_forwards(&x::whitespace()) {
+                    Some((space_sel, _)) => space_sel,
+                    None => separator_sel,
+                };
+                let value_start_idx = before_value_sel.token_range().1 + 1;
+
+                let value_sel = match before_value_sel.match_first_forwards(&x::attr::<Quoted>()) {
+                    Some((quoted_sel, _)) => quoted_sel,
+                    None => value_run(before_value_sel)?,
+                };
+
+                Some(value_sel.clone().finish_with_attr(KeyValue {
+                    key_range: key_sel.token_range(),
+                    value_range: (value_start_idx, value_sel.token_range().1),
+                }))
+            })
+            .collect()
+    }
+}
+
+/// Extends `selection` up to the next whitespace, without trailing `,` or `;`.
+///
+/// Returns None if there is no value token.
+fn value_run(mut selection: LLSelection) -> Option<LLSelection> {
+    let mut last_value_sel = None;
+
+    while let Some((next_sel, (tag, text))) =
+        selection.match_first_forwards(&x::all((x::attr::<TextTag>(), x::token_text())))
+    {
+        if *tag == TextTag::SPACE {
+            break;
+        }
+
+        if !(*tag == TextTag::PUNC && (text == "," || text == ";")) {
+            last_value_sel = Some(next_sel.clone());
+        }
+
+        selection = next_sel;
+    }
+
+    last_value_sel
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay, QuotedResolver};
+
+    let ll_line =
+        create_line_from_string(r#"level=warn user: "John Doe" count=3, path=/tmp/a.log see http://example.com url=http://x"#)
+            .run(&QuotedResolver::default())
+            .run(&KeyValueResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<KeyValue>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    level  =  warn     user  :     "  John     Doe  "     count  =  3  ,     path  =  /  tmp  /  a.log     see     http  :  /  /  example.com     url  =  http  :  /  /  x
+    ╰────────────╯KeyValue { key_range: (0, 0), value_range: (2, 2) }
+                       ╰────────────────────────────╯KeyValue { key_range: (4, 4), value_range: (7, 11) }
+                                                          ╰─────────╯KeyValue { key_range: (13, 13), value_range: (15, 15) }
+                                                                             ╰───────────────────────╯KeyValue { key_range: (18, 18), value_range: (20, 23) }
+                                                                                                                                                  ╰──────────────────────╯KeyValue { key_range: (33, 33), value_range: (35, 39) }
+    "###);
+}
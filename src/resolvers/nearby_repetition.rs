@@ -0,0 +1,72 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// A word which was already used shortly before.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Echo {
+    /// How many words back the previous occurrence is (`1` for adjacent words)
+    pub distance: usize,
+}
+
+/// Useful for style checking.
+///
+/// Assigns [Echo] to every [TextTag::WORD] which equals (case-insensitively) one
+/// of the `window` words before it. The window is counted in words, so spaces
+/// and punctuation in between are not taken into account.
+pub struct NearbyRepetitionResolver {
+    window: usize,
+    /// Lowercased words which never echo, like "the"
+    stopwords: Vec<String>,
+}
+
+impl NearbyRepetitionResolver {
+    pub fn new(window: usize, stopwords: &[&str]) -> Self {
+        NearbyRepetitionResolver {
+            window,
+            stopwords: stopwords.iter().map(|word| word.to_lowercase()).collect(),
+        }
+    }
+}
+
+impl Resolver for NearbyRepetitionResolver {
+    type Attr = Echo;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let words: Vec<(LLSelection, String)> = selection
+            .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .into_iter()
+            .map(|(sel, (_, text))| (sel, text.to_lowercase()))
+            .collect();
+
+        words
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, word))| !self.stopwords.contains(word))
+            .filter_map(|(idx, (sel, word))| {
+                let distance = words[idx.saturating_sub(self.window)..idx]
+                    .iter()
+                    .rev()
+                    .position(|(_, previous)| previous == word)?
+                    + 1;
+
+                Some(sel.finish_with_attr(Echo { distance }))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string("The cat saw the other cat, then the Cat ran")
+        .run(&NearbyRepetitionResolver::new(4, &["the"]));
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Echo>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    The     cat     saw     the     other     cat  ,     then     the     Cat     ran
+                                              ╰─╯Echo { distance: 4 }
+                                                                          ╰─╯Echo { distance: 3 }
+    "###);
+}
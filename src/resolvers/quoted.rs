@@ -0,0 +1,94 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// Span going from an opening quote mark to its closing quote mark, both included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Quoted;
+
+/// Marks quoted spans such as `"John Doe"` with [Quoted].
+///
+/// Unmatched opening quotes are ignored.
+pub struct QuotedResolver {
+    /// (opening, closing) quote marks
+    pairs: Vec<(char, char)>,
+}
+
+impl QuotedResolver {
+    pub fn new(pairs: Vec<(char, char)>) -> Self {
+        QuotedResolver { pairs }
+    }
+}
+
+impl Default for QuotedResolver {
+    /// Straight `"` and curly `“` `”` double quotes.
+    fn default() -> Self {
+        QuotedResolver::new(vec![('"', '"'), ('“', '”')])
+    }
+}
+
+impl Resolver for QuotedResolver {
+    type Attr = Quoted;
+
+    fn go(&self, mut search_range_sel: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let openings: Vec<char> = self.pairs.iter().map(|(opening, _)| *opening).collect();
+        let mut attrs = Vec::new();
+
+        while let Some((opening_sel, opening)) =
+            search_range_sel.find_first_by(&x::token_has_any(&openings))
+        {
+            let closing = self
+                .pairs
+                .iter()
+                .find(|(candidate, _)| candidate == opening)
+                .map(|(_, closing)| *closing)
+                .expect("opening comes from pairs");
+
+            // extend one token at a time until the closing quote mark
+            let mut selection = opening_sel.clone();
+            let quoted_sel = loop {
+                if let Some((closed_sel, _)) =
+                    selection.match_first_forwards(&x::token_has_any(&[closing]))
+                {
+                    break Some(closed_sel);
+                }
+
+                match selection.match_first_forwards(&x::attr::<TextTag>()) {
+                    Some((next_sel, _)) => selection = next_sel,
+                    None => break None,
+                }
+            };
+
+            let consumed_sel = match quoted_sel {
+                Some(quoted_sel) => {
+                    attrs.push(quoted_sel.finish_with_attr(Quoted));
+                    quoted_sel
+                }
+                None => opening_sel,
+            };
+
+            if let [_, Some(right_sel)] = search_range_sel.split_with(&consumed_sel) {
+                search_range_sel = right_sel;
+            } else {
+                break;
+            }
+        }
+
+        attrs
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string(r#"He said "stop", then “go” and "wait"#)
+        .run(&QuotedResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Quoted>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    He     said     "  stop  "  ,     then     “  go  ”     and     "  wait
+                    ╰────────╯Quoted
+                                               ╰──────╯Quoted
+    "###);
+}
@@ -0,0 +1,184 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// A cross-reference like `Section 3.2` or `Chapter IV`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reference {
+    /// The lowercased reference word, like `section`
+    pub kind: String,
+    /// As written, like `3.2`, `1a` or `IV`
+    pub id: String,
+}
+
+/// Recognizes a reference word followed by an id: a dotted number like
+/// `3.2`, with an optional letter suffix like `1a`, or a roman numeral like
+/// `IV`.
+pub struct ReferenceResolver {
+    /// Lowercased reference words
+    kinds: Vec<String>,
+}
+
+impl ReferenceResolver {
+    pub fn new(kinds: &[&str]) -> Self {
+        ReferenceResolver {
+            kinds: kinds.iter().map(|kind| kind.to_lowercase()).collect(),
+        }
+    }
+
+    /// Whether `text` is a well-formed roman numeral, all uppercase or all
+    /// lowercase, like `XIV` and not `IIII` or `VX`
+    fn is_roman(text: &str) -> bool {
+        let is_uppercase = text.chars().all(|ch| ch.is_ascii_uppercase());
+        if text.is_empty() || !(is_uppercase || text.chars().all(|ch| ch.is_ascii_lowercase())) {
+            return false;
+        }
+
+        let digit = |ch: char| match ch.to_ascii_uppercase() {
+            'I' => Some(1),
+            'V' => Some(5),
+            'X' => Some(10),
+            'L' => Some(50),
+            'C' => Some(100),
+            'D' => Some(500),
+            'M' => Some(1000),
+            _ => None,
+        };
+        let digits = match text.chars().map(digit).collect::<Option<Vec<i64>>>() {
+            Some(digits) => digits,
+            None => return false,
+        };
+
+        let mut value: i64 = 0;
+        for (idx, digit) in digits.iter().enumerate() {
+            match digits.get(idx + 1) {
+                Some(next) if next > digit => value -= digit,
+                _ => value += digit,
+            }
+        }
+
+        // well-formed numerals are the ones written back the same way
+        value > 0 && Self::to_roman(value as u32).eq_ignore_ascii_case(text)
+    }
+
+    fn to_roman(mut value: u32) -> String {
+        const NUMERALS: [(u32, &str); 13] = [
+            (1000, "M"),
+            (900, "CM"),
+            (500, "D"),
+            (400, "CD"),
+            (100, "C"),
+            (90, "XC"),
+            (50, "L"),
+            (40, "XL"),
+            (10, "X"),
+            (9, "IX"),
+            (5, "V"),
+            (4, "IV"),
+            (1, "I"),
+        ];
+
+        let mut roman = String::new();
+        for (numeral_value, numeral) in NUMERALS.iter() {
+            while value >= *numeral_value {
+                roman.push_str(numeral);
+                value -= numeral_value;
+            }
+        }
+        roman
+    }
+
+    /// Extends `number_sel` with the `.2` parts and the `a` suffix following it
+    fn match_number_id(number_sel: LLSelection, mut id: String) -> (LLSelection, String) {
+        let number = x::all((x::attr_eq(&TextTag::NATN), x::token_text()));
+        let mut selection = number_sel;
+
+        while let Some((part_sel, text)) = selection
+            .match_first_forwards(&x::token_has_any(&['.']))
+            .and_then(|(point_sel, _)| {
+                let (part_sel, (_, text)) = point_sel.match_first_forwards(&number)?;
+                Some((part_sel, text.to_string()))
+            })
+        {
+            id.push('.');
+            id.push_str(&text);
+            selection = part_sel;
+        }
+
+        // a suffix is right after the number, like `1a`
+        let suffix = selection
+            .match_first_forwards(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .filter(|(_, (_, text))| {
+                text.chars().count() <= 2 && text.chars().all(|ch| ch.is_ascii_lowercase())
+            })
+            .map(|(suffix_sel, (_, text))| (suffix_sel, text.to_string()));
+        if let Some((suffix_sel, text)) = suffix {
+            id.push_str(&text);
+            selection = suffix_sel;
+        }
+
+        (selection, id)
+    }
+
+    /// The id following the reference word `kind_sel`
+    fn match_id(kind_sel: &LLSelection) -> Option<(LLSelection, String)> {
+        let (space_sel, _) = kind_sel.match_first_forwards(&x::whitespace())?;
+        let (id_sel, (tag, text)) =
+            space_sel.match_first_forwards(&x::all((x::attr::<TextTag>(), x::token_text())))?;
+
+        match tag {
+            TextTag::NATN => Some(Self::match_number_id(id_sel, text.to_string())),
+            TextTag::WORD if Self::is_roman(text) => Some((id_sel, text.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl Default for ReferenceResolver {
+    /// English "section", "figure", "table", "chapter", "appendix",
+    /// "equation" and "page".
+    fn default() -> Self {
+        ReferenceResolver::new(&[
+            "section", "figure", "table", "chapter", "appendix", "equation", "page",
+        ])
+    }
+}
+
+impl Resolver for ReferenceResolver {
+    type Attr = Reference;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .into_iter()
+            .filter_map(|(kind_sel, (_, text))| {
+                let kind = text.to_lowercase();
+                if !self.kinds.contains(&kind) {
+                    return None;
+                }
+
+                let (reference_sel, id) = Self::match_id(&kind_sel)?;
+                Some(reference_sel.finish_with_attr(Reference { kind, id }))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string(
+        "See Section 3.2, Figure 4 and Table 1a in Chapter IV, not Chapter IIII or table of",
+    )
+    .run(&ReferenceResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Reference>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    See     Section     3  .  2  ,     Figure     4     and     Table     1  a     in     Chapter     IV  ,     not     Chapter     IIII     or     table     of
+            ╰─────────────────╯Reference { kind: "section", id: "3.2" }
+                                       ╰──────────╯Reference { kind: "figure", id: "4" }
+                                                                ╰────────────╯Reference { kind: "table", id: "1a" }
+                                                                                          ╰────────────╯Reference { kind: "chapter", id: "IV" }
+    "###);
+}
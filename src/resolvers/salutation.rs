@@ -0,0 +1,207 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SalutationKind {
+    /// Like `Dear John,`
+    Opening,
+    /// Like `Best regards,`
+    Closing,
+}
+
+/// Opening or closing of a letter, like `Dear John,` or `Best regards,`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Salutation {
+    pub kind: SalutationKind,
+    /// (start, end) token indexes of the name, as in `Dear John` or `Thanks, Ann`
+    pub name_range: Option<(usize, usize)>,
+}
+
+/// Longest name, as in `Dear Mary Jane Watson`
+const MAX_NAME_WORDS: usize = 3;
+
+/// Matches a salutation phrase at the start of the line, followed by an
+/// optional capitalized name, then a `,`, `!` or `:`, or the end of the line.
+///
+/// A closing may also be followed by the name after the comma when the name
+/// ends the line: `Thanks, Ann`.
+pub struct SalutationResolver {
+    /// Lowercased words of each phrase
+    phrases: Vec<(Vec<String>, SalutationKind)>,
+}
+
+impl SalutationResolver {
+    pub fn new(openings: &[&str], closings: &[&str]) -> Self {
+        let words = |phrase: &&str| -> Vec<String> {
+            phrase
+                .split_whitespace()
+                .map(|word| word.to_lowercase())
+                .collect()
+        };
+
+        SalutationResolver {
+            phrases: openings
+                .iter()
+                .map(|phrase| (words(phrase), SalutationKind::Opening))
+                .chain(
+                    closings
+                        .iter()
+                        .map(|phrase| (words(phrase), SalutationKind::Closing)),
+                )
+                .collect(),
+        }
+    }
+
+    /// The words of `phrase` at the start of `selection`, which has to be the line start
+    fn match_phrase(selection: &LLSelection, phrase: &[String]) -> Option<LLSelection> {
+        let word = x::all((x::attr_eq(&TextTag::WORD), x::token_text()));
+        let (first_word, rest) = phrase.split_first()?;
+
+        let (mut phrase_sel, (_, text)) = selection.find_first_by(&word)?;
+        if !phrase_sel.at_line_start() || text.to_lowercase() != *first_word {
+            return None;
+        }
+
+        for expected in rest {
+            let (space_sel, _) = phrase_sel.match_first_forwards(&x::whitespace())?;
+            let (word_sel, (_, text)) = space_sel.match_first_forwards(&word)?;
+            if text.to_lowercase() != *expected {
+                return None;
+            }
+            phrase_sel = word_sel;
+        }
+
+        Some(phrase_sel)
+    }
+
+    /// Extends `selection` with ` John Smith`, along with the name range
+    fn match_name(selection: &LLSelection) -> Option<(LLSelection, (usize, usize))> {
+        let word = x::all((x::attr_eq(&TextTag::WORD), x::token_text()));
+        let mut name_sel = selection.clone();
+        let mut name_start_idx = None;
+
+        for _ in 0..MAX_NAME_WORDS {
+            let next =
+                name_sel
+                    .match_first_forwards(&x::whitespace())
+                    .and_then(|(space_sel, _)| {
+                        let (word_sel, (_, text)) = space_sel.match_first_forwards(&word)?;
+                        Some((word_sel, text.starts_with(char::is_uppercase)))
+                    });
+
+            match next {
+                Some((word_sel, true)) => {
+                    name_start_idx.get_or_insert(word_sel.token_range().1);
+                    name_sel = word_sel;
+                }
+                _ => break,
+            }
+        }
+
+        let name_start_idx = name_start_idx?;
+        let name_end_idx = name_sel.token_range().1;
+        Some((name_sel, (name_start_idx, name_end_idx)))
+    }
+
+    fn match_salutation(
+        selection: &LLSelection,
+        phrase: &[String],
+        kind: SalutationKind,
+    ) -> Option<(LLSelection, Salutation)> {
+        let phrase_sel = Self::match_phrase(selection, phrase)?;
+        let (named_sel, mut name_range) = match Self::match_name(&phrase_sel) {
+            Some((name_sel, name_range)) => (name_sel, Some(name_range)),
+            None => (phrase_sel, None),
+        };
+
+        let mut salutation_sel =
+            match named_sel.match_first_forwards(&x::token_has_any(&[',', '!', ':'])) {
+                Some((punctuation_sel, _)) => punctuation_sel,
+                None if named_sel.at_line_end() => named_sel,
+                None => return None,
+            };
+
+        if kind == SalutationKind::Closing && name_range.is_none() {
+            if let Some((name_sel, signature_range)) = Self::match_name(&salutation_sel) {
+                if name_sel.at_line_end() {
+                    salutation_sel = name_sel;
+                    name_range = Some(signature_range);
+                }
+            }
+        }
+
+        Some((salutation_sel, Salutation { kind, name_range }))
+    }
+}
+
+impl Default for SalutationResolver {
+    /// Common English openings and closings, like "Dear" or "Best regards".
+    fn default() -> Self {
+        SalutationResolver::new(
+            &["Dear", "Hello", "Hi", "Hey", "Greetings", "Good morning"],
+            &[
+                "Best regards",
+                "Kind regards",
+                "Warm regards",
+                "Regards",
+                "Best wishes",
+                "Best",
+                "Sincerely",
+                "Yours sincerely",
+                "Yours truly",
+                "Cheers",
+                "Thanks",
+                "Thank you",
+            ],
+        )
+    }
+}
+
+impl Resolver for SalutationResolver {
+    type Attr = Salutation;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        self.phrases
+            .iter()
+            .filter_map(|(phrase, kind)| Self::match_salutation(&selection, phrase, *kind))
+            // longest match, so "Best regards," isn't also "Best"
+            .max_by_key(|(salutation_sel, _)| salutation_sel.token_range().1)
+            .map(|(salutation_sel, salutation)| salutation_sel.finish_with_attr(salutation))
+            .into_iter()
+            .collect()
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let displays: Vec<String> = [
+        "Dear John Smith, I hope you are well",
+        "Hi,",
+        "Best regards,",
+        "Thanks, Ann",
+        "Best practices are listed below",
+        "We say hi, then leave",
+    ]
+    .iter()
+    .map(|line| {
+        let ll_line = create_line_from_string(line).run(&SalutationResolver::default());
+        let mut ll_display = LLLineDisplay::new(&ll_line);
+        ll_display.include::<Salutation>();
+        ll_display.to_string()
+    })
+    .collect();
+
+    insta::assert_snapshot!(displays.join("\n"), @r###"
+    Dear     John     Smith  ,     I     hope     you     are     well
+    ╰────────────────────────╯Salutation { kind: Opening, name_range: Some((2, 4)) }
+    Hi  ,
+    ╰───╯Salutation { kind: Opening, name_range: None }
+    Best     regards  ,
+    ╰─────────────────╯Salutation { kind: Closing, name_range: None }
+    Thanks  ,     Ann
+    ╰───────────────╯Salutation { kind: Closing, name_range: Some((3, 3)) }
+    Best     practices     are     listed     below
+    We     say     hi  ,     then     leave
+    "###);
+}
@@ -0,0 +1,91 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// Span of a sentence, including its terminating punctuation but not the
+/// whitespace around it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Sentence;
+
+/// Splits the line into [Sentence]s.
+///
+/// A terminator only ends a sentence when it is followed by whitespace or the
+/// end of the line, so `1.5` or `a.log` don't split. Text after the last
+/// terminator is a sentence of its own.
+pub struct SentenceResolver {
+    terminators: Vec<char>,
+}
+
+impl SentenceResolver {
+    pub fn new(terminators: Vec<char>) -> Self {
+        SentenceResolver { terminators }
+    }
+}
+
+impl Default for SentenceResolver {
+    /// `.`, `!` and `?` terminators.
+    fn default() -> Self {
+        SentenceResolver::new(vec!['.', '!', '?'])
+    }
+}
+
+impl Resolver for SentenceResolver {
+    type Attr = Sentence;
+
+    fn go(&self, mut search_range_sel: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let mut attrs = Vec::new();
+
+        while let Some(trimmed_sel) = search_range_sel.trim_start(&x::whitespace()) {
+            let terminator_sel = trimmed_sel
+                .find_by(&x::token_has_any(&self.terminators))
+                .into_iter()
+                .map(|(sel, _)| sel)
+                .find(|sel| {
+                    sel.match_first_forwards(&x::attr::<TextTag>())
+                        .is_none_or(|(_, tag)| *tag == TextTag::SPACE)
+                });
+
+            let sentence_sel = match terminator_sel {
+                Some(terminator_sel) => match trimmed_sel.split_with(&terminator_sel) {
+                    [Some(before_sel), _] => {
+                        before_sel
+                            .match_first_forwards(&x::token_has_any(&self.terminators))
+                            .expect("terminator follows")
+                            .0
+                    }
+                    [None, _] => terminator_sel,
+                },
+                None => match trimmed_sel.trim_end(&x::whitespace()) {
+                    Some(sel) => sel,
+                    None => break,
+                },
+            };
+
+            attrs.push(sentence_sel.finish_with_attr(Sentence));
+
+            if let [_, Some(right_sel)] = search_range_sel.split_with(&sentence_sel) {
+                search_range_sel = right_sel;
+            } else {
+                break;
+            }
+        }
+
+        attrs
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string("It costs 1.5 euros. Really?!  Yes")
+        .run(&SentenceResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Sentence>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    It     costs     1  .  5     euros  .     Really  ?  !      Yes
+    ╰───────────────────────────────────╯Sentence
+                                              ╰──────────╯Sentence
+                                                                ╰─╯Sentence
+    "###);
+}
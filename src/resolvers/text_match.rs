@@ -12,7 +12,7 @@ pub struct TextMatchAssignResolver<T> {
     lookup: HashMap<String, Vec<T>>,
 }
 
-impl<T: Debug + Clone + 'static> TextMatchAssignResolver<T> {
+impl<T: Debug + Clone + Send + 'static> TextMatchAssignResolver<T> {
     pub fn new(lookup: HashMap<String, Vec<T>>) -> Self {
         TextMatchAssignResolver {
             case_sensitive: true,
@@ -39,7 +39,7 @@ impl<T: Debug + Clone + 'static> TextMatchAssignResolver<T> {
     }
 }
 
-impl<T: Debug + Clone + 'static> Resolver for TextMatchAssignResolver<T> {
+impl<T: Debug + Clone + Send + 'static> Resolver for TextMatchAssignResolver<T> {
     type Attr = T;
 
     fn go(&self, selection: crate::LLSelection) -> Vec<crate::LLCursorAssignment<Self::Attr>> {
@@ -99,7 +99,7 @@ fn test() {
     let mut ll_display = LLLineDisplay::new(&ll_line);
     ll_display.include::<Service>();
 
-    insta::assert_display_snapshot!(ll_display, @r###"
+    insta::assert_snapshot!(ll_display, @r###"
     when     Slack     hears     a     message     in     #  general  Algolia     search     query  :     message  ,     table
              ╰───╯Slack
                                                                       ╰─────╯Algolia
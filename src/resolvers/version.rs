@@ -0,0 +1,189 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// A version number like `v1.2.3` or `2.0.0-rc1`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Version {
+    pub parts: Vec<u64>,
+    /// Prerelease suffix, without the `-`
+    pub pre: Option<String>,
+}
+
+/// Recognizes dotted version numbers, with an optional `v` prefix and
+/// `-prerelease` suffix.
+///
+/// To tell them apart from other dotted numbers:
+///  * two parts are a decimal (`3.5`) unless there is a `v` prefix, a
+///    prerelease, or a capitalized name right before (`Python 3.11`)
+///  * four parts are an IP address (`10.0.0.1`) unless there is a `v` prefix
+///    or a prerelease
+#[derive(Default)]
+pub struct VersionResolver(());
+
+impl VersionResolver {
+    /// Parts of a single token like `v1.2.3`
+    fn prefixed_parts(text: &str) -> Option<Vec<u64>> {
+        let numbers = text.strip_prefix(|ch| ch == 'v' || ch == 'V')?;
+        let parts = numbers
+            .split('.')
+            .map(|part| {
+                if !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit()) {
+                    part.parse().ok()
+                } else {
+                    None
+                }
+            })
+            .collect::<Option<Vec<u64>>>()?;
+
+        if parts.len() > 1 {
+            Some(parts)
+        } else {
+            None
+        }
+    }
+
+    /// Extends `selection` with the `.2.3` parts following it
+    fn match_parts(selection: LLSelection, parts: &mut Vec<u64>) -> Option<LLSelection> {
+        let mut selection = selection;
+
+        while let Some((part_sel, text)) = selection
+            .match_first_forwards(&x::token_has_any(&['.']))
+            .and_then(|(point_sel, _)| {
+                let (part_sel, (_, text)) = point_sel
+                    .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))?;
+                Some((part_sel, text.to_string()))
+            })
+        {
+            parts.push(text.parse().ok()?);
+            selection = part_sel;
+        }
+
+        Some(selection)
+    }
+
+    /// Extends `selection` with a `-rc1` or `-beta.2` suffix
+    fn match_pre(selection: &LLSelection) -> Option<(LLSelection, String)> {
+        let alphanumeric = x::all((
+            x::any_of((x::attr_eq(&TextTag::WORD), x::attr_eq(&TextTag::NATN))),
+            x::token_text(),
+        ));
+
+        let (dash_sel, _) = selection.match_first_forwards(&x::token_has_any(&['-']))?;
+        let (mut pre_sel, (_, text)) = dash_sel.match_first_forwards(&alphanumeric)?;
+        let mut pre = text.to_string();
+
+        while let Some((next_sel, text)) = pre_sel
+            .match_first_forwards(&x::token_has_any(&['.']))
+            .and_then(|(point_sel, _)| {
+                let (next_sel, (_, text)) = point_sel.match_first_forwards(&alphanumeric)?;
+                Some((next_sel, text.to_string()))
+            })
+        {
+            pre.push('.');
+            pre.push_str(&text);
+            pre_sel = next_sel;
+        }
+
+        Some((pre_sel, pre))
+    }
+
+    /// Whether a capitalized word comes right before, like in `Python 3.11`
+    fn is_named(selection: &LLSelection) -> bool {
+        selection
+            .match_first_backwards(&x::whitespace())
+            .and_then(|(space_sel, _)| {
+                let (_, (_, text)) = space_sel.match_first_backwards(&x::all((
+                    x::attr_eq(&TextTag::WORD),
+                    x::token_text(),
+                )))?;
+                Some(text.starts_with(char::is_uppercase))
+            })
+            .unwrap_or(false)
+    }
+
+    /// The dotted numbers starting at `start_sel`, and the version they form if any
+    fn match_version(
+        start_sel: &LLSelection,
+        tag: &TextTag,
+        text: &str,
+    ) -> Option<(LLSelection, Option<Version>)> {
+        let (selection, parts, is_prefixed) = match tag {
+            TextTag::NATN => {
+                let mut parts = vec![text.parse().ok()?];
+                let selection = Self::match_parts(start_sel.clone(), &mut parts)?;
+                (selection, parts, false)
+            }
+            _ => (start_sel.clone(), Self::prefixed_parts(text)?, true),
+        };
+
+        let (version_sel, pre) = match Self::match_pre(&selection) {
+            Some((pre_sel, pre)) => (pre_sel, Some(pre)),
+            None => (selection, None),
+        };
+
+        let is_version = match parts.len() {
+            0 | 1 => false,
+            2 => is_prefixed || pre.is_some() || Self::is_named(start_sel),
+            4 => is_prefixed || pre.is_some(),
+            _ => true,
+        };
+
+        Some((
+            version_sel,
+            if is_version {
+                Some(Version { parts, pre })
+            } else {
+                None
+            },
+        ))
+    }
+}
+
+impl Resolver for VersionResolver {
+    type Attr = Version;
+
+    fn go(&self, mut search_range_sel: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let mut attrs = Vec::new();
+
+        while let Some((start_sel, (tag, text))) =
+            search_range_sel.find_first_by(&x::all((x::attr::<TextTag>(), x::token_text())))
+        {
+            // skip rejected dotted numbers as a whole, so `0.0.1` isn't taken out of `10.0.0.1`
+            let consumed_sel = match Self::match_version(&start_sel, tag, text) {
+                Some((version_sel, Some(version))) => {
+                    attrs.push(version_sel.finish_with_attr(version));
+                    version_sel
+                }
+                Some((rejected_sel, None)) => rejected_sel,
+                None => start_sel,
+            };
+
+            if let [_, Some(right_sel)] = search_range_sel.split_with(&consumed_sel) {
+                search_range_sel = right_sel;
+            } else {
+                break;
+            }
+        }
+
+        attrs
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string(
+        "Upgrade v1.2.3 to 2.0.0-rc1 on Python 3.11, not 10.0.0.1 for 3.5 dollars",
+    )
+    .run(&VersionResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Version>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    Upgrade     v1.2.3     to     2  .  0  .  0  -  rc1     on     Python     3  .  11  ,     not     10  .  0  .  0  .  1     for     3  .  5     dollars
+                ╰────╯Version { parts: [1, 2, 3], pre: None }
+                                  ╰───────────────────╯Version { parts: [2, 0, 0], pre: Some("rc1") }
+                                                                              ╰──────╯Version { parts: [3, 11], pre: None }
+    "###);
+}
@@ -1,4 +1,5 @@
 mod currency_amount;
+mod ll_line;
 mod ll_selection;
 mod tokenizing;
 
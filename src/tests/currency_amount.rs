@@ -28,6 +28,7 @@ impl Resolver for CurrencySymbolResolver {
 }
 
 #[derive(Clone, Debug)]
+#[allow(dead_code)]
 struct Amount(Decimal);
 
 struct AmountResolver {
@@ -107,6 +108,7 @@ impl Resolver for AmountResolver {
 }
 
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 struct CurrencyAmount(CurrencySymbol, Amount);
 
 struct CurrencyAmountResolver;
@@ -139,7 +141,7 @@ fn it_works_usd() {
     ll_line_display.include::<Amount>();
     ll_line_display.include::<CurrencyAmount>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     $  1  ,  000  .  25
     ╰USDDollars
        ╰──────────────╯Amount(1000.25)
@@ -161,7 +163,7 @@ fn it_works_euro() {
     ll_line_display.include::<Amount>();
     ll_line_display.include::<CurrencyAmount>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     .     1     000  ,  25  €
                             ╰Euro
           ╰──────────────╯Amount(1000.25)
@@ -179,7 +181,7 @@ fn trailing_delimiter() {
     let mut ll_line_display = LLLineDisplay::new(&ll_line);
     ll_line_display.include::<Amount>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     100  ,  .
     ╰─╯Amount(100)
     "###);
@@ -0,0 +1,1149 @@
+use super::*;
+use crate::ll_line::{x, FinishWith};
+
+#[test]
+fn slice() {
+    let ll_line = test_line("Hello big world").run(&TestResolver(|sel: LLSelection| {
+        sel.find_by(&x::seq((x::token_text(), x::whitespace(), x::token_text())))
+            .finish_with(|(first, _, last)| format!("{} {}", first, last))
+    }));
+
+    let sliced = ll_line.slice((2, 4));
+
+    let mut ll_line_display = LLLineDisplay::new(&sliced);
+    ll_line_display.include::<String>();
+    ll_line_display.include::<TextTag>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    big     world
+    ╰───────────╯"big world"
+    ╰─╯WORD
+         ╰SPACE
+            ╰───╯WORD
+    "###);
+    insta::assert_snapshot!(format!("{:?}", sliced.ll_tokens()), @r###"
+    [LLToken { token_idx: 0, pos_starts_at: 0, pos_ends_at: 3, token: Text("big", WORD) }, LLToken { token_idx: 1, pos_starts_at: 3, pos_ends_at: 4, token: Text(" ", SPACE) }, LLToken { token_idx: 2, pos_starts_at: 4, pos_ends_at: 9, token: Text("world", WORD) }]
+    "###);
+}
+
+#[test]
+fn longest_attr() {
+    let ll_line = test_line("a bb ccc dd")
+        .run(&TestResolver(|sel: LLSelection| {
+            sel.find_by(&x::seq((x::token_text(), x::whitespace(), x::token_text())))
+                .finish_with(|(first, _, last)| format!("{} {}", first, last))
+        }))
+        .run(&TestResolver(|sel: LLSelection| {
+            let pair = x::seq((x::token_text(), x::whitespace(), x::token_text()));
+            sel.find_by(&x::seq((x::token_text(), x::whitespace(), pair)))
+                .finish_with(|(first, _, (_, _, last))| format!("{} .. {}", first, last))
+        }));
+
+    // "a .. ccc" and "bb .. dd" both span five tokens
+    assert_eq!(
+        ll_line.longest_attr::<String>(),
+        Some(((0, 4), &String::from("a .. ccc")))
+    );
+    assert_eq!(
+        ll_line.longest_attr::<TextTag>(),
+        Some(((0, 0), &TextTag::WORD))
+    );
+    assert_eq!(ll_line.longest_attr::<u32>(), None);
+}
+
+#[test]
+fn resolver_fn() {
+    let ll_line = test_line("Hello big world").run(&crate::resolver_fn(|sel: LLSelection| {
+        sel.find_by(&x::attr_eq(&TextTag::WORD))
+            .into_iter()
+            .enumerate()
+            .map(|(word_idx, (word_sel, _))| word_sel.finish_with_attr(word_idx))
+            .collect()
+    }));
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<usize>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Hello     big     world
+    ╰───╯0
+              ╰─╯1
+                      ╰───╯2
+    "###);
+}
+
+#[test]
+fn sentences() {
+    use crate::SentenceResolver;
+    use std::rc::Rc;
+
+    let ll_line = Rc::new(test_line("Hello there. How are you? Fine"));
+    assert_eq!(ll_line.sentences().count(), 0);
+
+    let ll_line =
+        Rc::new(test_line("Hello there. How are you? Fine").run(&SentenceResolver::default()));
+    let sentences: Vec<_> = ll_line
+        .sentences()
+        .map(|sentence_sel| {
+            let words = sentence_sel.find_by(&x::attr_eq(&TextTag::WORD)).len();
+            (sentence_sel.token_range(), words)
+        })
+        .collect();
+
+    assert_eq!(sentences, vec![((0, 3), 2), ((5, 10), 3), ((12, 12), 1)]);
+}
+
+#[test]
+fn resolver_idempotent() {
+    let ll_line = crate::assert_resolver_idempotent(
+        test_line("Hello big world"),
+        &TestResolver(|sel: LLSelection| {
+            sel.find_by(&x::token_text())
+                .finish_with(|text| text.to_uppercase())
+        }),
+    );
+
+    assert_eq!(ll_line.query::<String>().len(), 5);
+}
+
+#[test]
+#[should_panic(expected = "resolver is not idempotent")]
+fn resolver_not_idempotent() {
+    // counts the values it assigned before
+    crate::assert_resolver_idempotent(
+        test_line("Hello big world"),
+        &TestResolver(|sel: LLSelection| {
+            sel.find_by(&x::attr_eq(&TextTag::WORD))
+                .into_iter()
+                .map(|(word_sel, _)| {
+                    let count = word_sel.find_by(&x::attr::<String>()).len();
+                    word_sel.finish_with_attr(count.to_string())
+                })
+                .collect()
+        }),
+    );
+}
+
+#[test]
+fn remove_auto_attrs() {
+    let mut ll_line = test_line("Hello, big world").run(&crate::resolver_fn(|sel: LLSelection| {
+        sel.find_by(&x::token_text())
+            .into_iter()
+            .filter(|(_, text)| *text == "big")
+            .map(|(big_sel, _)| big_sel.finish_with_attr(TextTag::SYMB))
+            .collect()
+    }));
+    ll_line.remove_auto_attrs();
+
+    assert!(ll_line.find(&x::whitespace()).is_empty());
+    assert!(ll_line.find(&x::token_has_any(&[','])).is_empty());
+    assert_eq!(ll_line.find(&x::token_text()).len(), 6);
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<TextTag>();
+    ll_line_display.include::<char>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Hello  ,     big     world
+                 ╰─╯SYMB
+    "###);
+}
+
+#[test]
+fn unannotated_tokens() {
+    let ll_line = test_line("a=1 and b").run(&crate::KeyValueResolver::default());
+
+    assert_eq!(ll_line.unannotated_tokens(), vec![3, 4, 5, 6]);
+    assert_eq!(test_line("").unannotated_tokens(), Vec::<usize>::new());
+}
+
+#[test]
+fn attr_from_byte_range() {
+    use crate::ll_line::ByteRangeError;
+
+    let mut ll_line = test_line("Héllo, big world");
+    let big_start = "Héllo, ".len();
+
+    assert_eq!(
+        ll_line.attr_from_byte_range(big_start, big_start + 3, String::from("big")),
+        Ok((3, 3))
+    );
+    assert_eq!(
+        ll_line.attr_from_byte_range(0, "Héllo, big world".len(), String::from("all")),
+        Ok((0, 5))
+    );
+    assert_eq!(
+        ll_line.attr_from_byte_range(big_start + 1, big_start + 5, String::from("ig w")),
+        Err(ByteRangeError::Misaligned {
+            covering_range: (3, 5),
+            covering_bytes: (big_start, big_start + 9),
+        })
+    );
+    assert_eq!(
+        ll_line.attr_from_byte_range(big_start, 100, String::from("past the end")),
+        Err(ByteRangeError::InvalidRange)
+    );
+    assert_eq!(
+        ll_line.attr_from_byte_range(big_start, big_start, String::from("empty")),
+        Err(ByteRangeError::InvalidRange)
+    );
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<String>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Héllo  ,     big     world
+                 ╰─╯"big"
+    ╰────────────────────────╯"all"
+    "###);
+}
+
+#[test]
+fn word_ngrams() {
+    let ll_line = test_line("the big  red dog, it ran 2 miles");
+
+    assert_eq!(
+        ll_line.word_ngrams(2),
+        vec![
+            ((0, 2), String::from("the big")),
+            ((2, 4), String::from("big red")),
+            ((4, 6), String::from("red dog")),
+            ((9, 11), String::from("it ran"))
+        ]
+    );
+    assert_eq!(
+        ll_line.word_ngrams(3),
+        vec![
+            ((0, 4), String::from("the big red")),
+            ((2, 6), String::from("big red dog"))
+        ]
+    );
+    assert!(ll_line.word_ngrams(0).is_empty());
+    assert!(ll_line.word_ngrams(5).is_empty());
+}
+
+#[test]
+fn assignment_priority() {
+    // assigns `(priority, label)` with `priority` to every word
+    let label_words = |priority: i32, label: &'static str| {
+        crate::resolver_fn(move |sel: LLSelection| {
+            sel.find_by(&x::attr_eq(&TextTag::WORD))
+                .into_iter()
+                .map(|(word_sel, _)| {
+                    word_sel
+                        .finish_with_attr((priority, label))
+                        .with_priority(priority)
+                })
+                .collect()
+        })
+    };
+
+    let ll_line = test_line("Hello world")
+        .run(&label_words(0, "first"))
+        .run(&label_words(0, "tie"))
+        .run(&label_words(2, "high"))
+        .run(&label_words(1, "low"))
+        .run(&label_words(2, "second high"));
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<(i32, &str)>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Hello     world
+    ╰───╯(2, "second high")
+    ╰───╯(2, "high")
+              ╰───╯(2, "second high")
+              ╰───╯(2, "high")
+    "###);
+}
+
+#[test]
+fn run_observed() {
+    let mut observed = Vec::new();
+    let ll_line = test_line("Hello big world").run_observed(
+        &crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::attr_eq(&TextTag::WORD))
+                .into_iter()
+                .map(|(word_sel, _)| word_sel.finish_with_attr(word_sel.token_range().0))
+                .collect()
+        }),
+        &mut |range, debug| observed.push(format!("{:?} {}", range, debug)),
+    );
+
+    assert_eq!(observed, ["(0, 0) 0", "(2, 2) 2", "(4, 4) 4"]);
+    assert_eq!(ll_line.query::<usize>().len(), 3);
+}
+
+#[test]
+fn debug_dump() {
+    let ll_line = test_line("Hi you").run(&crate::resolver_fn(|sel: LLSelection| {
+        vec![sel.finish_with_attr(sel.token_range().1)]
+    }));
+
+    insta::assert_snapshot!(ll_line.debug_dump(), @r###"
+    starts_at:
+      0: (0, 0) layered_nlp::ll_line::TextTag, (0, 2) usize
+      1: (1, 1) char, (1, 1) layered_nlp::ll_line::TextTag
+      2: (2, 2) layered_nlp::ll_line::TextTag
+    ends_at:
+      0: (0, 0) layered_nlp::ll_line::TextTag
+      1: (1, 1) char, (1, 1) layered_nlp::ll_line::TextTag
+      2: (0, 2) usize, (2, 2) layered_nlp::ll_line::TextTag
+    ranges:
+      char: [(1, 1)]
+      layered_nlp::ll_line::TextTag: [(0, 0), (1, 1), (2, 2)]
+      usize: [(0, 2)]
+    "###);
+}
+
+#[test]
+fn invalidate_byte_range() {
+    use crate::ll_line::ByteRangeError;
+    use std::any::TypeId;
+
+    // assigns the word index to each word, and the word count to the line
+    let count_words = crate::resolver_fn(|sel: LLSelection| {
+        let words = sel.find_by(&x::attr_eq(&TextTag::WORD));
+        let mut assignments: Vec<_> = words
+            .iter()
+            .enumerate()
+            .map(|(word_idx, (word_sel, _))| word_sel.finish_with_attr(word_idx))
+            .collect();
+        assignments.push(sel.finish_with_attr(words.len()));
+        assignments
+    });
+
+    // "Hello" is bytes 0 to 5, "big" 6 to 9, "world" 10 to 15
+    let mut ll_line = test_line("Hello big world").run(&count_words);
+    assert_eq!(
+        ll_line.invalidate_byte_range(7, 8),
+        Ok(vec![
+            ((0, 4), TypeId::of::<usize>()),
+            ((2, 2), TypeId::of::<usize>())
+        ])
+    );
+    assert_eq!(ll_line.invalidate_byte_range(5, 6), Ok(vec![]));
+    // an insertion touches the tokens on both sides
+    assert_eq!(
+        ll_line.invalidate_byte_range(10, 10),
+        Ok(vec![((4, 4), TypeId::of::<usize>())])
+    );
+    // an edit of an outdated text
+    assert_eq!(
+        ll_line.invalidate_byte_range(12, 20),
+        Err(ByteRangeError::InvalidRange)
+    );
+    assert_eq!(
+        ll_line.invalidate_byte_range(8, 7),
+        Err(ByteRangeError::InvalidRange)
+    );
+
+    // "rock", "'", "n", "'", "roll" are bytes 0 to 4, 4 to 5, 5 to 6, 6 to 7, 7 to 11
+    let mut apostrophes_line = test_line("rock'n'roll").run(&count_words);
+    assert_eq!(
+        apostrophes_line.invalidate_byte_range(7, 11),
+        Ok(vec![
+            ((0, 4), TypeId::of::<usize>()),
+            ((4, 4), TypeId::of::<usize>())
+        ])
+    );
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<usize>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Hello     big     world
+    ╰───╯0
+    "###);
+}
+
+#[test]
+fn find_backwards() {
+    let ll_line = test_line("Paid $5 and $ 12, not 7$");
+
+    // single tokens come out the same both ways
+    assert_eq!(
+        format!("{:?}", ll_line.find_backwards(&x::attr_eq(&TextTag::NATN))),
+        format!("{:?}", ll_line.find(&x::attr_eq(&TextTag::NATN))),
+    );
+
+    // backwards, a sequence starts from its last token
+    let dollars = x::seq((
+        x::all((x::attr_eq(&TextTag::NATN), x::token_text())),
+        x::token_has_any(&['$']),
+    ));
+    insta::assert_debug_snapshot!(ll_line.find_backwards(&dollars), @r###"
+    [
+        LLLineFind {
+            start: 5,
+            end: 7,
+            found: (
+                (
+                    (),
+                    "5",
+                ),
+                '$',
+            ),
+        },
+    ]
+    "###);
+}
+
+#[test]
+fn retract() {
+    use crate::LLCursorAssignment;
+
+    #[derive(Clone, Debug)]
+    struct Number;
+    #[derive(Clone, Debug)]
+    struct Year;
+
+    let ll_line = test_line("In 1999 and 12")
+        .run(&crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::attr_eq(&TextTag::NATN))
+                .finish_with(|_| Number)
+        }))
+        .run(&crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::all((x::attr::<Number>(), x::token_text())))
+                .into_iter()
+                .filter(|(_, (_, text))| text.len() == 4)
+                .flat_map(|(year_sel, _)| {
+                    vec![
+                        LLCursorAssignment::retract::<Number>(year_sel.token_range()),
+                        year_sel.finish_with_attr(Year),
+                    ]
+                })
+                .collect()
+        }));
+
+    assert_eq!(ll_line.query::<Number>().len(), 1);
+    assert_eq!(ll_line.query::<Year>().len(), 1);
+    // the other types at the range remain
+    assert_eq!(ll_line.find(&x::attr_eq(&TextTag::NATN)).len(), 2);
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Number>();
+    ll_line_display.include::<Year>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    In     1999     and     12
+                            ╰╯Number
+           ╰──╯Year
+    "###);
+}
+
+#[test]
+fn value_tokens() {
+    use crate::{LToken, TokenValue};
+
+    #[derive(Clone, Debug)]
+    struct UserId(u32);
+    #[derive(Clone, Debug)]
+    struct Mention;
+
+    let ll_line = create_line_from_input_tokens(
+        vec![
+            InputToken::text("Thanks ".to_string(), Vec::new()),
+            InputToken::value(
+                1,
+                TokenValue::new(UserId(42)).with_placeholder("@sam"),
+                Vec::new(),
+            ),
+            InputToken::text(" and ".to_string(), Vec::new()),
+            InputToken::value(1, TokenValue::new(UserId(7)), Vec::new()),
+        ],
+        |text| text.encode_utf16().count(),
+    )
+    .run(&crate::resolver_fn(|sel: LLSelection| {
+        sel.find_by(&x::attr::<UserId>()).finish_with(|_| Mention)
+    }));
+
+    match &ll_line.ll_tokens()[2].token() {
+        LToken::Value(token_value) => {
+            assert_eq!(token_value.placeholder(), "@sam");
+            assert_eq!(token_value.get::<UserId>().map(|id| id.0), Some(42));
+            assert!(token_value.get::<Mention>().is_none());
+        }
+        token => panic!("expected a value token, got {:?}", token),
+    }
+    assert_eq!(
+        ll_line
+            .query::<Mention>()
+            .into_iter()
+            .map(|(range, text, _)| (range, text))
+            .collect::<Vec<_>>(),
+        vec![((2, 2), "@sam".to_string()), ((6, 6), String::new())]
+    );
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<UserId>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Thanks     @sam     and     <>
+               ╰──╯UserId(42)
+                                ╰╯UserId(7)
+    "###);
+}
+
+#[test]
+fn query_at() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Greeting;
+
+    let ll_line = test_line("hi there, you").run(&crate::resolver_fn(|sel: LLSelection| {
+        sel.find_by(&x::seq((
+            x::attr_eq(&TextTag::WORD),
+            x::whitespace(),
+            x::attr_eq(&TextTag::WORD),
+        )))
+        .finish_with(|_| Greeting)
+    }));
+
+    // inside `there`
+    assert_eq!(
+        ll_line.query_at(5),
+        vec![
+            ((0, 2), "layered_nlp::tests::ll_line::query_at::Greeting"),
+            ((2, 2), "layered_nlp::ll_line::TextTag")
+        ]
+    );
+    // the boundary of `hi` and the space is in the space
+    assert_eq!(
+        ll_line.query_type_at::<Greeting>(2),
+        vec![((0, 2), vec![&Greeting])]
+    );
+    assert_eq!(
+        ll_line.query_type_at::<TextTag>(2),
+        vec![((1, 1), vec![&TextTag::SPACE])]
+    );
+    // the space after the comma is only itself
+    assert_eq!(
+        ll_line.query_at(9),
+        vec![((4, 4), "char"), ((4, 4), "layered_nlp::ll_line::TextTag")]
+    );
+    assert!(ll_line.query_type_at::<Greeting>(9).is_empty());
+    // past the end
+    assert!(ll_line.query_at(13).is_empty());
+}
+
+#[test]
+fn display_layers() {
+    use std::any::TypeId;
+
+    #[derive(Clone, Debug)]
+    struct Word;
+    #[derive(Clone, Debug)]
+    struct Pair;
+
+    let ll_line = test_line("hi there!")
+        .run(&crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::attr_eq(&TextTag::WORD))
+                .finish_with(|_| Word)
+        }))
+        .run(&crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::seq((
+                x::attr::<Word>(),
+                x::whitespace(),
+                x::attr::<Word>(),
+            )))
+            .finish_with(|_| Pair)
+        }));
+
+    let ll_line_display = LLLineDisplay::new(&ll_line)
+        .with_all()
+        .exclude::<char>()
+        .order_layers_by(|type_id| type_id != TypeId::of::<TextTag>());
+    insta::assert_snapshot!(ll_line_display, @r###"
+    hi     there  !
+    ╰╯WORD
+        ╰SPACE
+           ╰───╯WORD
+                  ╰PUNC
+    ╰──────────╯Pair
+    ╰╯Word
+           ╰───╯Word
+    "###);
+
+    let ll_line_display = LLLineDisplay::new(&ll_line)
+        .with::<Pair>()
+        .with::<Word>()
+        .with::<TextTag>()
+        .only::<Word>()
+        .only::<Pair>();
+    insta::assert_snapshot!(ll_line_display, @r###"
+    hi     there  !
+    ╰──────────╯Pair
+    ╰╯Word
+           ╰───╯Word
+    "###);
+}
+
+#[test]
+fn run_all() {
+    use crate::ResolverPipeline;
+
+    #[derive(Clone, Debug)]
+    struct Word;
+    #[derive(Clone, Debug)]
+    struct Pair;
+
+    let words = || {
+        crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::attr_eq(&TextTag::WORD))
+                .finish_with(|_| Word)
+        })
+    };
+    let pairs = || {
+        crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::seq((
+                x::attr::<Word>(),
+                x::whitespace(),
+                x::attr::<Word>(),
+            )))
+            .finish_with(|_| Pair)
+        })
+    };
+
+    let mut pipeline = ResolverPipeline::new().with(words());
+    pipeline.push(pairs());
+
+    let ll_line = test_line("one two 3 four").run_all(&pipeline);
+    assert_eq!(
+        ll_line.debug_dump(),
+        test_line("one two 3 four")
+            .run(&words())
+            .run(&pairs())
+            .debug_dump()
+    );
+    assert_eq!(ll_line.query::<Pair>().len(), 1);
+
+    // in the other order, there are no words to pair yet
+    let ll_line =
+        test_line("one two 3 four").run_all(&ResolverPipeline::new().with(pairs()).with(words()));
+    assert!(ll_line.query::<Pair>().is_empty());
+    assert_eq!(ll_line.query::<Word>().len(), 3);
+    assert_eq!(
+        test_line("one")
+            .run_all(&ResolverPipeline::new())
+            .debug_dump(),
+        test_line("one").debug_dump()
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn run_batch() {
+    use crate::ResolverPipeline;
+
+    #[derive(Clone, Debug)]
+    struct Word;
+    #[derive(Clone, Debug)]
+    struct Pair;
+
+    let pipeline = ResolverPipeline::new()
+        .with(crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::attr_eq(&TextTag::WORD))
+                .finish_with(|_| Word)
+        }))
+        .with(crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::seq((
+                x::attr::<Word>(),
+                x::whitespace(),
+                x::attr::<Word>(),
+            )))
+            .finish_with(|_| Pair)
+        }));
+
+    let texts: Vec<String> = (0..200)
+        .map(|i| match i % 3 {
+            0 => format!("line {} has words", i),
+            1 => format!("{} {}", i, i * 2),
+            _ => String::new(),
+        })
+        .collect();
+
+    let sequential: Vec<String> = texts
+        .iter()
+        .map(|text| test_line(text).run_all(&pipeline).debug_dump())
+        .collect();
+    let parallel: Vec<String> = pipeline
+        .run_batch(texts.iter().map(|text| test_line(text)).collect())
+        .iter()
+        .map(LLLine::debug_dump)
+        .collect();
+
+    assert_eq!(parallel, sequential);
+}
+
+#[test]
+fn char_and_byte_ranges() {
+    use crate::{create_line_from_string, LToken};
+    use std::rc::Rc;
+
+    let text = "Go 😀 東京, ok";
+    let ll_line = create_line_from_string(text);
+
+    let ranges: Vec<_> = ll_line
+        .ll_tokens()
+        .iter()
+        .map(|ll_token| {
+            let idx = ll_token.token_idx();
+            (ll_token.char_range(), ll_line.byte_range((idx, idx)))
+        })
+        .collect();
+    // (chars, bytes) of `Go`, ` `, `😀`, ` `, `東`, `京`, `,`, ` `, `ok`
+    assert_eq!(
+        ranges,
+        vec![
+            ((0, 2), (0, 2)),
+            ((2, 3), (2, 3)),
+            ((3, 4), (3, 7)),
+            ((4, 5), (7, 8)),
+            ((5, 6), (8, 11)),
+            ((6, 7), (11, 14)),
+            ((7, 8), (14, 15)),
+            ((8, 9), (15, 16)),
+            ((9, 11), (16, 18)),
+        ]
+    );
+
+    for (ll_token, (_, (byte_start, byte_end))) in ll_line.ll_tokens().iter().zip(&ranges) {
+        match ll_token.token() {
+            LToken::Text(token_text, _) => assert_eq!(&text[*byte_start..*byte_end], token_text),
+            LToken::Value(_) => unreachable!(),
+        }
+    }
+
+    // found ranges are in chars too
+    let found = ll_line.find(&x::attr_eq(&TextTag::PUNC));
+    assert_eq!(
+        found.iter().map(|found| found.range()).collect::<Vec<_>>(),
+        vec![(7, 8)]
+    );
+
+    let ll_line = Rc::new(ll_line);
+    let line_sel = LLSelection::from_line(ll_line).unwrap();
+    assert_eq!(line_sel.range(), (0, text.chars().count()));
+    assert_eq!(line_sel.byte_range(), (0, text.len()));
+}
+
+#[test]
+fn char_and_byte_ranges_apostrophes() {
+    use crate::{create_line_from_string, LToken};
+
+    // every apostrophe is a token, even after the first one of a word
+    for text in ["rock'n'roll ''x", "Ça s'é'tait '' l'été'"] {
+        let ll_line = create_line_from_string(text);
+        let chars: Vec<char> = text.chars().collect();
+
+        for ll_token in ll_line.ll_tokens() {
+            let token_text = match ll_token.token() {
+                LToken::Text(token_text, _) => token_text,
+                LToken::Value(_) => unreachable!(),
+            };
+            let idx = ll_token.token_idx();
+            let (byte_start, byte_end) = ll_line.byte_range((idx, idx));
+            assert_eq!(&text[byte_start..byte_end], token_text, "{:?}", text);
+
+            let (char_start, char_end) = ll_token.char_range();
+            let source: String = chars[char_start..char_end].iter().collect();
+            assert_eq!(&source, token_text, "{:?}", text);
+        }
+
+        let last_token = ll_line.ll_tokens().last().unwrap();
+        assert_eq!(last_token.pos_ends_at(), chars.len(), "{:?}", text);
+    }
+}
+
+#[test]
+fn assignment_metadata() {
+    use crate::{LLCursorAssignment, Metadata};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Reading(&'static str);
+
+    let ll_line = test_line("1/2 or 3")
+        .run(&crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::seq((
+                x::attr_eq(&TextTag::NATN),
+                x::token_has_any(&['/']),
+                x::attr_eq(&TextTag::NATN),
+            )))
+            .into_iter()
+            .map(|(sel, _)| {
+                sel.finish_with_attr(Reading("date"))
+                    .with_confidence(0.4)
+                    .with_source("dates")
+            })
+            .collect()
+        }))
+        .run(&crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::seq((
+                x::attr_eq(&TextTag::NATN),
+                x::token_has_any(&['/']),
+                x::attr_eq(&TextTag::NATN),
+            )))
+            .into_iter()
+            .map(|(sel, _)| {
+                sel.finish_with_attr(Reading("fraction"))
+                    .with_confidence(0.7)
+            })
+            .collect()
+        }))
+        .run(&crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::attr_eq(&TextTag::NATN))
+                .finish_with(|_| Reading("number"))
+        }));
+
+    let fraction = Metadata {
+        confidence: 0.7,
+        source: "",
+    };
+    let mut readings = ll_line.query_with_metadata::<Reading>();
+    readings.sort_by_key(|(range, _, _)| *range);
+    assert_eq!(
+        readings,
+        vec![
+            ((0, 0), "1".to_string(), vec![(&Reading("number"), None)]),
+            (
+                (0, 2),
+                "1/2".to_string(),
+                vec![
+                    (
+                        &Reading("date"),
+                        Some(&Metadata {
+                            confidence: 0.4,
+                            source: "dates",
+                        })
+                    ),
+                    (&Reading("fraction"), Some(&fraction)),
+                ]
+            ),
+            ((2, 2), "2".to_string(), vec![(&Reading("number"), None)]),
+            ((6, 6), "3".to_string(), vec![(&Reading("number"), None)]),
+        ]
+    );
+
+    // keep the most confident of competing readings
+    let ll_line = ll_line.run(&crate::resolver_fn(|sel: LLSelection| {
+        sel.find_by(&x::attr::<Reading>())
+            .into_iter()
+            .filter_map(|(reading_sel, _)| {
+                let readings = reading_sel.attrs_with_metadata::<Reading>();
+                if readings.len() < 2 {
+                    return None;
+                }
+                let (reading, metadata) = readings
+                    .into_iter()
+                    .max_by(|(_, a), (_, b)| {
+                        let confidence = |m: &Option<&Metadata>| m.map_or(1.0, |m| m.confidence);
+                        confidence(a).partial_cmp(&confidence(b)).unwrap()
+                    })
+                    .unwrap();
+                let mut reassigned = reading_sel
+                    .finish_with_attr(reading.clone())
+                    .with_confidence(metadata.map_or(1.0, |m| m.confidence));
+                if let Some(metadata) = metadata {
+                    reassigned = reassigned.with_source(metadata.source);
+                }
+                Some(vec![
+                    LLCursorAssignment::retract::<Reading>(reading_sel.token_range()),
+                    reassigned,
+                ])
+            })
+            .flatten()
+            .collect()
+    }));
+
+    let readings = ll_line.query_with_metadata::<Reading>();
+    assert_eq!(
+        readings
+            .iter()
+            .find(|(range, _, _)| *range == (0, 2))
+            .unwrap()
+            .2,
+        vec![(&Reading("fraction"), Some(&fraction))]
+    );
+    // plain queries are unchanged
+    assert_eq!(ll_line.query::<Reading>().len(), 4);
+}
+
+#[test]
+fn query_overlapping() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct Entity(&'static str);
+
+    // tokens: `New`, ` `, `York`, ` `, `to`, ` `, `Paris`
+    let ll_line = test_line("New York to Paris").run(&crate::resolver_fn(|sel: LLSelection| {
+        let (city_sel, _) = sel.find_first_by(&x::token_text()).unwrap();
+        let (city_sel, _) = city_sel.match_first_forwards(&x::whitespace()).unwrap();
+        let (city_sel, _) = city_sel.match_first_forwards(&x::token_text()).unwrap();
+        let (paris_sel, _) = sel
+            .find_by(&x::token_text())
+            .into_iter()
+            .find(|(_, text)| *text == "Paris")
+            .unwrap();
+
+        vec![
+            city_sel.finish_with_attr(Entity("New York")),
+            city_sel.finish_with_attr(Entity("NY")),
+            paris_sel.finish_with_attr(Entity("Paris")),
+        ]
+    }));
+
+    let new_york = [((0, 2), &Entity("New York")), ((0, 2), &Entity("NY"))];
+    assert_eq!(ll_line.query_overlapping::<Entity>((1, 3)), new_york);
+    // sharing a single token at either end overlaps
+    assert_eq!(ll_line.query_overlapping::<Entity>((2, 2)), new_york);
+    assert_eq!(
+        ll_line.query_overlapping::<Entity>((2, 6)),
+        [new_york[0], new_york[1], ((6, 6), &Entity("Paris"))]
+    );
+    assert!(ll_line.query_overlapping::<Entity>((3, 5)).is_empty());
+
+    assert_eq!(ll_line.query_contained_in::<Entity>((0, 2)), new_york);
+    assert!(ll_line.query_contained_in::<Entity>((1, 5)).is_empty());
+    assert_eq!(
+        ll_line.query_contained_in::<Entity>((1, 6)),
+        [((6, 6), &Entity("Paris"))]
+    );
+}
+
+#[test]
+fn merge_range() {
+    use std::any::TypeId;
+
+    // assigns the word index to each word, the line to the line, and the
+    // word pairs starting with `York` or `in`
+    let label = crate::resolver_fn(|sel: LLSelection| {
+        let mut assignments: Vec<LLCursorAssignment<&str>> = sel
+            .find_by(&x::seq((x::token_text(), x::whitespace(), x::token_text())))
+            .into_iter()
+            .filter(|(_, (first, _, _))| *first == "York" || *first == "in")
+            .map(|(pair_sel, _)| pair_sel.finish_with_attr("pair"))
+            .collect();
+        assignments.push(sel.finish_with_attr("line"));
+        assignments
+    });
+    let count_words = crate::resolver_fn(|sel: LLSelection| {
+        sel.find_by(&x::attr_eq(&TextTag::WORD))
+            .iter()
+            .enumerate()
+            .map(|(word_idx, (word_sel, _))| word_sel.finish_with_attr(word_idx))
+            .collect()
+    });
+
+    // "in", " ", "New", " ", "York", " ", "City", " ", "now"
+    let mut ll_line = test_line("in New York City now")
+        .run(&count_words)
+        .run(&label);
+    assert_eq!(
+        ll_line.merge_range((2, 4)),
+        (
+            2,
+            vec![
+                ((0, 2), TypeId::of::<&str>()),
+                ((4, 6), TypeId::of::<&str>())
+            ]
+        )
+    );
+    let ll_tokens = ll_line.ll_tokens();
+    assert_eq!(ll_tokens.len(), 7);
+    assert_eq!(
+        (ll_tokens[2].pos_starts_at, ll_tokens[2].pos_ends_at),
+        (3, 11)
+    );
+    assert_eq!(ll_tokens[6].token_idx, 6);
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<TextTag>();
+    ll_line_display.include::<usize>();
+    ll_line_display.include::<&str>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    in     New York     City     now
+           ╰──────╯WORD
+    ╰╯WORD
+        ╰SPACE
+                     ╰SPACE
+                        ╰──╯WORD
+                              ╰SPACE
+                                 ╰─╯WORD
+    ╰╯0
+           ╰──────╯2
+           ╰──────╯1
+                        ╰──╯3
+                                 ╰─╯4
+    ╰──────────────────────────────╯"line"
+    "###);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn tokens_json() {
+    let ll_line = test_line("$1 ok");
+    let json = ll_line.tokens_to_json();
+
+    insta::assert_snapshot!(json, @r###"
+    [{"token_idx":0,"pos_starts_at":0,"pos_ends_at":1,"token":{"Text":["$","SYMB"]}},{"token_idx":1,"pos_starts_at":1,"pos_ends_at":2,"token":{"Text":["1","NATN"]}},{"token_idx":2,"pos_starts_at":2,"pos_ends_at":3,"token":{"Text":[" ","SPACE"]}},{"token_idx":3,"pos_starts_at":3,"pos_ends_at":5,"token":{"Text":["ok","WORD"]}}]
+    "###);
+
+    let restored = LLLine::tokens_from_json(&json).unwrap();
+    assert_eq!(
+        format!("{:?}", restored.ll_tokens()),
+        format!("{:?}", ll_line.ll_tokens())
+    );
+    assert_eq!(restored.query::<char>(), ll_line.query::<char>());
+    assert_eq!(restored.query::<TextTag>(), ll_line.query::<TextTag>());
+
+    assert!(
+        LLLine::tokens_from_json(&json.replacen("\"token_idx\":0", "\"token_idx\":1", 1)).is_err()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn line_json() {
+    use crate::AttrRegistry;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    struct Label(String);
+
+    let ll_line = test_line("Hi you!")
+        .run(&crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+                .into_iter()
+                .map(|(word_sel, (_, text))| {
+                    word_sel
+                        .finish_with_attr(Label(text.to_uppercase()))
+                        .with_priority(1)
+                })
+                .chain(std::iter::once(sel.finish_with_attr(Label("all".into()))))
+                .collect()
+        }))
+        .run(&crate::resolver_fn(|sel: LLSelection| {
+            vec![sel.finish_with_attr(3usize)]
+        }));
+
+    let registry = AttrRegistry::new().register::<Label>("Label");
+    let json = ll_line.to_json(&registry);
+    insta::assert_snapshot!(json, @r###"
+    {"tokens":[{"token_idx":0,"pos_starts_at":0,"pos_ends_at":2,"token":{"Text":["Hi","WORD"]}},{"token_idx":1,"pos_starts_at":2,"pos_ends_at":3,"token":{"Text":[" ","SPACE"]}},{"token_idx":2,"pos_starts_at":3,"pos_ends_at":6,"token":{"Text":["you","WORD"]}},{"token_idx":3,"pos_starts_at":6,"pos_ends_at":7,"token":{"Text":["!","PUNC"]}}],"attrs":[{"type":"char","assigned":[{"range":[1,1],"values":[" "]},{"range":[3,3],"values":["!"]}]},{"type":"TextTag","assigned":[{"range":[0,0],"values":["WORD"]},{"range":[1,1],"values":["SPACE"]},{"range":[2,2],"values":["WORD"]},{"range":[3,3],"values":["PUNC"]}]},{"type":"Label","assigned":[{"range":[0,0],"values":["HI"],"priority":1},{"range":[2,2],"values":["YOU"],"priority":1},{"range":[0,3],"values":["all"]}]}]}
+    "###);
+
+    let restored = LLLine::from_json(&json, &registry).unwrap();
+    // unregistered types are left out
+    assert!(restored.query::<usize>().is_empty());
+    assert_eq!(
+        restored.debug_dump(),
+        ll_line
+            .debug_dump()
+            .replace(", (0, 3) usize", "")
+            .replace("  usize: [(0, 3)]\n", "")
+    );
+    assert_eq!(restored.query::<char>(), ll_line.query::<char>());
+    assert_eq!(restored.query::<TextTag>(), ll_line.query::<TextTag>());
+    assert_eq!(
+        format!("{:?}", restored.query::<Label>()),
+        format!("{:?}", ll_line.query::<Label>())
+    );
+
+    // removed attributes stay removed
+    let mut ll_line = restored;
+    ll_line.remove_auto_attrs();
+    let restored = LLLine::from_json(&ll_line.to_json(&registry), &registry).unwrap();
+    assert_eq!(restored.debug_dump(), ll_line.debug_dump());
+    assert_eq!(restored.find(&x::token_text()).len(), 4);
+
+    assert!(LLLine::from_json(&json, &AttrRegistry::new()).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn line_json_metadata() {
+    use crate::AttrRegistry;
+
+    let ll_line = test_line("Hi").run(&crate::resolver_fn(|sel: LLSelection| {
+        vec![sel.finish_with_attr(1usize).with_confidence(0.5)]
+    }));
+    assert!(ll_line.query_with_metadata::<usize>()[0].2[0].1.is_some());
+
+    // metadata is not saved
+    let registry = AttrRegistry::new().register::<usize>("usize");
+    let restored = LLLine::from_json(&ll_line.to_json(&registry), &registry).unwrap();
+    let restored_values = restored.query_with_metadata::<usize>();
+    assert_eq!(restored_values[0].2, vec![(&1, None)]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn value_tokens_json() {
+    use crate::{AttrRegistry, TokenValue};
+
+    #[derive(Clone, Debug)]
+    struct UserId;
+
+    let exclamations = crate::resolver_fn(|sel: LLSelection| {
+        sel.find_by(&x::token_has_any(&['!']))
+            .finish_with(|_| "exclamation")
+    });
+    let ll_line = create_line_from_input_tokens(
+        vec![
+            InputToken::text("Hi ".to_string(), Vec::new()),
+            InputToken::value(1, TokenValue::new(UserId), Vec::new()),
+            InputToken::value(1, TokenValue::default(), Vec::new()),
+            InputToken::text("!".to_string(), Vec::new()),
+        ],
+        |text| text.encode_utf16().count(),
+    );
+    let json = ll_line.to_json(&AttrRegistry::new());
+    assert_eq!(ll_line.run(&exclamations).query::<&str>().len(), 1);
+
+    // `UserId` is not registered, its value token comes back without a value
+    let restored = LLLine::from_json(&json, &AttrRegistry::new())
+        .unwrap()
+        .run(&exclamations);
+    assert_eq!(restored.query::<&str>().len(), 1);
+    assert!(restored.query::<UserId>().is_empty());
+}
+
+#[test]
+fn from_tokens() {
+    use crate::ll_line::{LLToken, LToken};
+
+    let ll_line = LLLine::from_tokens(vec![
+        LLToken::new(0, 0, 3, LToken::Text("let".to_string(), TextTag::WORD)),
+        LLToken::new(1, 3, 4, LToken::Text(" ".to_string(), TextTag::SPACE)),
+        LLToken::new(2, 4, 5, LToken::Text("x".to_string(), TextTag::WORD)),
+        LLToken::new(3, 5, 6, LToken::Text("=".to_string(), TextTag::SYMB)),
+    ]);
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<char>();
+    ll_line_display.include::<TextTag>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    let     x  =
+         ╰' '
+            ╰'x'
+               ╰'='
+    ╰─╯WORD
+         ╰SPACE
+            ╰WORD
+               ╰SYMB
+    "###);
+    assert_eq!(ll_line.ll_tokens()[3].pos_starts_at(), 5);
+}
+
+#[test]
+#[should_panic(expected = "token_idx matches its index")]
+fn from_tokens_out_of_order() {
+    use crate::ll_line::{LLToken, LToken};
+
+    LLLine::from_tokens(vec![LLToken::new(
+        1,
+        0,
+        1,
+        LToken::Text("x".to_string(), TextTag::WORD),
+    )]);
+}
@@ -9,12 +9,12 @@ fn find_by() {
             .finish_with(|_| String::from("here"))
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
                     ╰"here"
                                      ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                     ╰"here"
                        ╰"here"
@@ -22,19 +22,19 @@ fn find_by() {
                                            ╰"here"
                                               ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰"here"
                        ╰"here"
                                         ╰"here"
                                                            ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰"here"
            ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @r###"
     .
     ╰"here"
     "###);
@@ -51,23 +51,23 @@ fn find_first_by() {
             .finish_with(|_| String::from("here"))
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
                     ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                     ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @r###"
     .
     ╰"here"
     "###);
@@ -86,29 +86,97 @@ fn split_by() {
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
     ╰────────────╯"here"
                        ╰──────────╯"here"
                                         ╰────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
     ╰────────────╯"here"
                              ╰──────────╯"here"
                                                  ╰────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
        ╰────────────╯"here"
                           ╰──────────╯"here"
                                            ╰────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
        ╰╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
+}
+
+#[test]
+fn split_on() {
+    use crate::ll_line::{x, FinishWith, LLSelection};
+    use crate::tests::test_line;
+    use std::rc::Rc;
+
+    #[derive(Clone, Debug)]
+    struct Comma;
+
+    let spans = |text: &str| {
+        let ll_line = Rc::new(test_line(text).run(&crate::resolver_fn(|sel: LLSelection| {
+            sel.find_by(&x::token_has_any(&[',']))
+                .finish_with(|_| Comma)
+        })));
+        let line_sel = LLSelection::from_line(ll_line).unwrap();
+
+        let split: Vec<_> = line_sel
+            .split_on::<Comma>()
+            .into_iter()
+            .map(|sel| {
+                // panics unless on the same line
+                assert!(sel.split_with(&line_sel).iter().all(Option::is_none));
+                sel.text()
+            })
+            .collect();
+        let keeping_empty: Vec<_> = line_sel
+            .split_on_keeping_empty::<Comma>()
+            .into_iter()
+            .map(|sel| sel.map(|sel| sel.text()))
+            .collect();
+        (split, keeping_empty)
+    };
+
+    assert_eq!(
+        spans("red, green, and blue"),
+        (
+            vec![
+                "red".to_string(),
+                " green".to_string(),
+                " and blue".to_string()
+            ],
+            vec![
+                Some("red".to_string()),
+                Some(" green".to_string()),
+                Some(" and blue".to_string())
+            ]
+        )
+    );
+    assert_eq!(
+        spans(",red,,blue,"),
+        (
+            vec!["red".to_string(), "blue".to_string()],
+            vec![
+                None,
+                Some("red".to_string()),
+                None,
+                Some("blue".to_string()),
+                None
+            ]
+        )
+    );
+    assert_eq!(
+        spans("red"),
+        (vec!["red".to_string()], vec![Some("red".to_string())])
+    );
+    assert_eq!(spans(","), (Vec::new(), vec![None, None]));
 }
 
 #[test]
@@ -122,27 +190,81 @@ fn find_by_seq() {
             .finish_with(|_| String::from("here"))
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
                     ╰────╯"here"
                                      ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                           ╰────╯"here"
                                               ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰─────╯"here"
                        ╰────╯"here"
                                         ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰───╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
+}
+
+#[test]
+fn opt_in_seq() {
+    use crate::ll_line::{x, FinishWith, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    let measurement = x::seq((
+        x::attr_eq(&TextTag::NATN),
+        x::opt(x::whitespace()),
+        x::all((x::attr_eq(&TextTag::WORD), x::token_text())),
+    ));
+
+    insta::assert_snapshot!(test_resolver("5 km, 7 and 3km", |sel: LLSelection| {
+        sel.find_by(&measurement)
+            .finish_with(|(_, space, (_, unit))| format!("{} spaced {}", unit, space.is_some()))
+    }), @r###"
+    5     km  ,     7     and     3  km
+    ╰──────╯"km spaced true"
+                    ╰───────╯"and spaced true"
+                                  ╰───╯"km spaced false"
+    "###);
+    // going backwards from the unit, at the start of the line
+    insta::assert_snapshot!(test_resolver("5 km, 3km", |sel: LLSelection| {
+        sel.find_by(&x::attr_eq(&TextTag::WORD))
+            .into_iter()
+            .filter_map(|(unit_sel, _)| {
+                unit_sel.match_first_backwards(&x::seq((
+                    x::opt(x::whitespace()),
+                    x::attr_eq(&TextTag::NATN),
+                )))
+            })
+            .finish_with(|(space, _)| format!("spaced {}", space.is_some()))
+    }), @r###"
+    5     km  ,     3  km
+    ╰──────╯"spaced true"
+                    ╰───╯"spaced false"
+    "###);
+    // an optional last matcher matches at the end of the line
+    insta::assert_snapshot!(test_resolver("km 3", |sel: LLSelection| {
+        sel.find_by(&x::seq((x::attr_eq(&TextTag::NATN), x::opt(x::whitespace()))))
+            .finish_with(|(_, space)| format!("spaced {}", space.is_some()))
+    }), @r###"
+    km     3
+           ╰"spaced false"
+    "###);
+    // alone, it only finds its matches
+    insta::assert_snapshot!(test_resolver("3 km", |sel: LLSelection| {
+        sel.find_by(&x::opt(x::attr_eq(&TextTag::NATN)))
+            .finish_with(|number| format!("{:?}", number))
+    }), @r###"
+    3     km
+    ╰"Some(())"
+    "###);
 }
 
 #[test]
@@ -154,35 +276,33 @@ fn match_seq_forwards() {
         range_sel
             .find_by(&x::attr::<TextTag>())
             .into_iter()
-            .map(|(sel, _)| {
+            .flat_map(|(sel, _)| {
                 sel.match_forwards(&x::seq((
                     x::attr_eq(&TextTag::NATN),
                     x::attr_eq(&TextTag::WORD),
                 )))
             })
-            .into_iter()
-            .flatten()
             .finish_with(|_| String::from("here"))
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
                     ╰────────╯"here"
                                      ╰─────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                           ╰────────╯"here"
                                               ╰─────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰─────────╯"here"
                        ╰────────╯"here"
                                         ╰─────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -194,35 +314,33 @@ fn match_seq_backwards() {
         range_sel
             .find_by(&x::attr::<TextTag>())
             .into_iter()
-            .map(|(sel, _)| {
+            .flat_map(|(sel, _)| {
                 sel.match_backwards(&x::seq((
                     x::attr_eq(&TextTag::NATN),
                     x::attr_eq(&TextTag::WORD),
                 )))
             })
-            .into_iter()
-            .flatten()
             .finish_with(|_| String::from("here"))
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
           ╰─────────╯"here"
                             ╰────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
           ╰─────────╯"here"
                                   ╰────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
              ╰─────────╯"here"
                                ╰────────╯"here"
                                                  ╰─────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -235,32 +353,31 @@ fn one_of() {
             .find_by(&x::attr_eq(&TextTag::NATN))
             .into_iter()
             .flat_map(|(sel, _)| Some(sel.match_first_forwards(&x::token_has_any(&['(', '.']))?.0))
-            .into_iter()
             .map(|sel| sel.finish_with_attr(String::from("here")))
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
               ╰─────╯"here"
                                 ╰────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
               ╰─────╯"here"
                                       ╰────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
                  ╰─────╯"here"
                                    ╰────╯"here"
                                                      ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
        ╰───╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -273,32 +390,31 @@ fn one_of_backwards() {
             .find_by(&x::attr_eq(&TextTag::NATN))
             .into_iter()
             .flat_map(|(sel, _)| Some(sel.match_first_backwards(&x::token_has_any(&['(', '.']))?.0))
-            .into_iter()
             .map(|sel| sel.finish_with_attr(String::from("here")))
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
                     ╰────╯"here"
                                      ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                           ╰────╯"here"
                                               ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰─────╯"here"
                        ╰────╯"here"
                                         ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰───╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -319,20 +435,19 @@ fn one_of_seq() {
                     .0,
                 )
             })
-            .into_iter()
             .map(|sel| sel.finish_with_attr(String::from("here")))
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @"0000  aa  0000  .  000  aa  000  .  0000  aa  0000");
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @"0000  aa  0000  .  000  aa  000  .  0000  aa  0000");
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
               ╰────────╯"here"
                                       ╰───────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @".  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .");
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @".  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .");
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -353,20 +468,19 @@ fn one_of_seq_backwards() {
                     .0,
                 )
             })
-            .into_iter()
             .map(|sel| sel.finish_with_attr(String::from("here")))
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @"0000  aa  0000  .  000  aa  000  .  0000  aa  0000");
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @"0000  aa  0000  .  000  aa  000  .  0000  aa  0000");
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                        ╰───────╯"here"
                                            ╰────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @".  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .");
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @".  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .");
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -382,23 +496,23 @@ fn trim_start() {
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
           ╰──────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
           ╰───────────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰──────────────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰──────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @r###"
     .
     ╰"here"
     "###);
@@ -417,23 +531,23 @@ fn trim_end() {
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
     ╰──────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
     ╰───────────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰──────────────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰──────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @r###"
     .
     ╰"here"
     "###);
@@ -452,24 +566,206 @@ fn trim() {
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
           ╰────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
           ╰─────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰──────────────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰──────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @r###"
     .
     ╰"here"
     "###);
 }
+
+#[test]
+fn attr_matches() {
+    use crate::ll_line::{
+        x, FinishWith, LLCursorAssignment, LLLineDisplay, LLSelection, Resolver, TextTag,
+    };
+    use crate::tests::{test_line, TestResolver};
+
+    #[derive(Debug, Clone)]
+    struct Number {
+        value: u32,
+        ordinal: bool,
+    }
+
+    impl x::AttrMatches for Number {
+        fn matches(&self, candidate: &Self) -> bool {
+            self.value == candidate.value
+        }
+    }
+
+    struct NumberResolver;
+
+    impl Resolver for NumberResolver {
+        type Attr = Number;
+
+        fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+            selection
+                .find_by(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))
+                .into_iter()
+                .map(|(sel, (_, text))| {
+                    let ordinal = sel
+                        .match_first_forwards(&x::token_text())
+                        .is_some_and(|(_, suffix)| suffix == "st");
+
+                    sel.finish_with_attr(Number {
+                        value: text.parse().unwrap(),
+                        ordinal,
+                    })
+                })
+                .collect()
+        }
+    }
+
+    let ll_line = test_line("1st and 1, not 2")
+        .run(&NumberResolver)
+        .run(&TestResolver(|sel: LLSelection| {
+            sel.find_by(&x::attr_matches(&Number {
+                value: 1,
+                ordinal: false,
+            }))
+            .finish_with(|number| format!("ordinal: {}", number.ordinal))
+        }));
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<String>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    1  st     and     1  ,     not     2
+    ╰"ordinal: true"
+                      ╰"ordinal: false"
+    "###);
+}
+
+#[test]
+fn at_line_start_and_end() {
+    use crate::ll_line::{x, LLSelection};
+    use crate::tests::test_line;
+    use std::rc::Rc;
+
+    let ll_line = Rc::new(test_line("  Hello big world "));
+    let words: Vec<_> = LLSelection::from_line(ll_line)
+        .unwrap()
+        .find_by(&x::token_text())
+        .into_iter()
+        .filter(|(_, text)| text.trim() != "")
+        .map(|(word_sel, _)| (word_sel.at_line_start(), word_sel.at_line_end()))
+        .collect();
+
+    assert_eq!(words, vec![(true, false), (false, false), (false, true)]);
+}
+
+#[test]
+fn custom_matcher() {
+    use crate::ll_line::x::{self, ToIdx, XDirection, XMatch};
+    use crate::ll_line::{FinishWith, LLLine, LToken, TextTag};
+    use crate::tests::test_resolver;
+
+    /// Matches the token at the position, as long as it is a word of `len` chars, and yields its
+    /// index
+    struct WordOfLen(usize);
+
+    impl<'l> XMatch<'l> for WordOfLen {
+        type Out = usize;
+
+        fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+        where
+            M: XDirection<'l>,
+        {
+            let idx = direction.idx();
+            match ll_line.ll_tokens()[idx].token() {
+                LToken::Text(text, TextTag::WORD) if text.chars().count() == self.0 => {
+                    vec![(idx, ToIdx::new(idx))]
+                }
+                _ => Vec::new(),
+            }
+        }
+    }
+
+    insta::assert_snapshot!(test_resolver("one three two four", |sel| {
+        sel.find_by(&x::seq((WordOfLen(3), x::whitespace(), x::token_text())))
+            .finish_with(|(idx, _, next)| format!("{} then {}", idx, next))
+    }), @r###"
+    one     three     two     four
+    ╰───────────╯"0 then three"
+                      ╰──────────╯"4 then four"
+    "###);
+    insta::assert_snapshot!(test_resolver("one three two four", |sel| {
+        sel.find_by(&x::attr_eq(&TextTag::SPACE))
+            .into_iter()
+            .filter_map(|(space_sel, _)| space_sel.match_first_backwards(&WordOfLen(3)))
+            .finish_with(|idx| format!("after {}", idx))
+    }), @r###"
+    one     three     two     four
+    ╰────╯"after 0"
+                      ╰────╯"after 4"
+    "###);
+}
+
+#[test]
+fn text_and_range() {
+    use crate::ll_line::{x, LLSelection, TextTag};
+    use crate::tests::test_line;
+    use std::rc::Rc;
+
+    // sizes are in UTF-16 code units
+    let ll_line = Rc::new(test_line("Hi, 😀 wörld!"));
+    let line_sel = LLSelection::from_line(ll_line).unwrap();
+    assert_eq!(line_sel.text(), "Hi, 😀 wörld!");
+    assert_eq!(line_sel.range(), (0, 13));
+
+    let (comma_sel, _) = line_sel.find_first_by(&x::attr_eq(&TextTag::PUNC)).unwrap();
+    assert_eq!(
+        (comma_sel.text(), comma_sel.range()),
+        (",".to_string(), (2, 3))
+    );
+
+    let (space_sel, _) = comma_sel.match_first_forwards(&x::whitespace()).unwrap();
+    assert_eq!(
+        (space_sel.text(), space_sel.range()),
+        (", ".to_string(), (2, 4))
+    );
+
+    let spaces: Vec<_> = line_sel
+        .find_by(&x::attr_eq(&TextTag::SPACE))
+        .into_iter()
+        .map(|(space_sel, _)| (space_sel.text(), space_sel.range()))
+        .collect();
+    assert_eq!(
+        spaces,
+        vec![(" ".to_string(), (3, 4)), (" ".to_string(), (6, 7))]
+    );
+}
+
+#[test]
+fn extend_to() {
+    use crate::ll_line::{x, LLSelection, TextTag};
+    use crate::tests::test_line;
+    use std::rc::Rc;
+
+    let ll_line = Rc::new(test_line("1 and 2, 3"));
+    let line_sel = LLSelection::from_line(ll_line).unwrap();
+    let numbers: Vec<_> = line_sel
+        .find_by(&x::attr_eq(&TextTag::NATN))
+        .into_iter()
+        .map(|(number_sel, _)| number_sel)
+        .collect();
+
+    let numbers_sel = numbers[0].extend_to(&numbers[2]);
+    assert_eq!(numbers_sel.token_range(), (0, 7));
+    assert_eq!(numbers_sel.text(), "1 and 2, 3");
+    assert_eq!(numbers[1].extend_to(&numbers[1]), numbers[1]);
+}
@@ -12,29 +12,65 @@ use std::{
 /// and is used internally for collecting attribute assignments.
 // `Box<dyn Bucket>` is the empty bucket for this type.
 // It is required to add a type not present in `TypeBucket`.
-pub struct AnyAttribute(TypeId, Box<dyn Bucket>, Box<dyn Any>);
+pub struct AnyAttribute(TypeId, Box<dyn Bucket>, Box<dyn Any + Send>, ValueFns);
+
+/// What the value of an [AnyAttribute] can do, knowing its type
+#[derive(Clone, Copy)]
+struct ValueFns {
+    clone: fn(&dyn Any) -> Box<dyn Any + Send>,
+    debug: fn(&dyn Any, &mut fmt::Formatter<'_>) -> fmt::Result,
+}
 
 impl std::fmt::Debug for AnyAttribute {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("AnyAttribute").finish()
+        struct DebugValue<'a>(&'a AnyAttribute);
+        impl Debug for DebugValue<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                ((self.0).3.debug)(&*(self.0).2, f)
+            }
+        }
+
+        f.debug_tuple("AnyAttribute")
+            .field(&DebugValue(self))
+            .finish()
+    }
+}
+
+impl Clone for AnyAttribute {
+    fn clone(&self) -> Self {
+        AnyAttribute(
+            self.0,
+            self.1.clone_bucket(),
+            (self.3.clone)(&*self.2),
+            self.3,
+        )
     }
 }
 
 impl AnyAttribute {
-    pub fn new<T: 'static + Debug>(value: T) -> Self {
+    pub fn new<T: 'static + Debug + Clone + Send>(value: T) -> Self {
         AnyAttribute(
             TypeId::of::<T>(),
             Box::new(Vec::<T>::new()),
             Box::new(value),
+            ValueFns {
+                clone: |value| Box::new(value.downcast_ref::<T>().unwrap().clone()),
+                debug: |value, f| Debug::fmt(value.downcast_ref::<T>().unwrap(), f),
+            },
         )
     }
 
     pub fn extract<T: 'static>(self) -> Result<T, Self> {
-        let AnyAttribute(key, empty_bucket, value) = self;
+        let AnyAttribute(key, empty_bucket, value, value_fns) = self;
         value
             .downcast()
             .map(|boxed| *boxed)
-            .map_err(|e| AnyAttribute(key, empty_bucket, e))
+            .map_err(|e| AnyAttribute(key, empty_bucket, e, value_fns))
+    }
+
+    /// The value, if it is a `T`
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        self.2.downcast_ref()
     }
 
     pub fn type_id(&self) -> TypeId {
@@ -131,20 +167,39 @@ pub struct TypeBucket {
     map: HashMap<TypeId, Box<dyn Bucket>>,
 }
 
+impl Clone for TypeBucket {
+    fn clone(&self) -> Self {
+        TypeBucket {
+            map: self
+                .map
+                .iter()
+                .map(|(type_id, bucket)| (*type_id, bucket.clone_bucket()))
+                .collect(),
+        }
+    }
+}
+
 impl fmt::Debug for dyn Bucket {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Bucket::debug(self, f)
     }
 }
 
-trait Bucket {
+/// `Send` so that lines can be moved across threads
+trait Bucket: Send {
     fn as_any(&self) -> &dyn Any
     where
         Self: 'static;
     fn as_any_mut(&mut self) -> &mut dyn Any
     where
         Self: 'static;
-    fn insert_any(&mut self, val: Box<dyn Any>);
+    fn insert_any(&mut self, val: Box<dyn Any + Send>);
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+    fn append_bucket(&mut self, other: Box<dyn Bucket>);
+    fn count(&self) -> usize;
+    fn clone_bucket(&self) -> Box<dyn Bucket>;
+    fn type_name(&self) -> &'static str;
+    fn debug_values(&self) -> Vec<String>;
     fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.pad("Bucket")
     }
@@ -156,7 +211,7 @@ trait Bucket {
     }
 }
 
-impl<T: 'static + Debug> Bucket for Vec<T> {
+impl<T: 'static + Debug + Clone + Send> Bucket for Vec<T> {
     #[inline]
     fn as_any(&self) -> &dyn Any {
         self
@@ -165,9 +220,32 @@ impl<T: 'static + Debug> Bucket for Vec<T> {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
-    fn insert_any(&mut self, val: Box<dyn Any>) {
+    fn insert_any(&mut self, val: Box<dyn Any + Send>) {
         self.push(*val.downcast().expect("type doesn't match"));
     }
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+    fn append_bucket(&mut self, other: Box<dyn Bucket>) {
+        self.extend(
+            *other
+                .into_any()
+                .downcast::<Self>()
+                .expect("type doesn't match"),
+        );
+    }
+    fn count(&self) -> usize {
+        self.len()
+    }
+    fn clone_bucket(&self) -> Box<dyn Bucket> {
+        Box::new(self.clone())
+    }
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
+    fn debug_values(&self) -> Vec<String> {
+        self.iter().map(|item| format!("{:?}", item)).collect()
+    }
 
     fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Debug::fmt(self, f)
@@ -186,7 +264,7 @@ impl TypeBucket {
     /// Insert a prepared `KvPair` into this `TypeBucket`.
     ///
     /// If a value of this type already exists, it will be returned.
-    pub fn insert_any_attribute(&mut self, AnyAttribute(key, empty_value, value): AnyAttribute) {
+    pub fn insert_any_attribute(&mut self, AnyAttribute(key, empty_value, value, _): AnyAttribute) {
         self.map.entry(key).or_insert(empty_value).insert_any(value)
     }
 
@@ -194,7 +272,7 @@ impl TypeBucket {
     ///
     /// If a value of this type already exists, it will be returned.
     #[track_caller]
-    pub fn insert<T: 'static + Debug>(&mut self, val: T) {
+    pub fn insert<T: 'static + Debug + Clone + Send>(&mut self, val: T) {
         self.map
             .entry(TypeId::of::<T>())
             .or_insert_with(|| Box::new(Vec::<T>::new()))
@@ -204,13 +282,20 @@ impl TypeBucket {
             .push(val);
     }
 
-    // /// Check if container contains value for type
-    // pub fn contains<T: 'static>(&self) -> bool {
-    //     self.map
-    //         .as_ref()
-    //         .and_then(|m| m.get(&TypeId::of::<T>()))
-    //         .is_some()
-    // }
+    /// Check if container contains value for type
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Check if container contains values of the type `type_id`
+    pub fn contains_any(&self, type_id: TypeId) -> bool {
+        self.map.contains_key(&type_id)
+    }
+
+    /// The number of values of the type `type_id`
+    pub fn count_any(&self, type_id: TypeId) -> usize {
+        self.map.get(&type_id).map_or(0, |bucket| bucket.count())
+    }
 
     /// Get a reference to a value previously inserted on this `TypeBucket`.
     pub fn get<T: 'static>(&self) -> &[T] {
@@ -222,16 +307,14 @@ impl TypeBucket {
     }
 
     pub fn get_debug<T: 'static + Debug>(&self) -> Vec<String> {
+        self.get_debug_any(TypeId::of::<T>())
+    }
+
+    /// The `{:?}` of each value of the type `type_id`
+    pub fn get_debug_any(&self, type_id: TypeId) -> Vec<String> {
         self.map
-            .get(&TypeId::of::<T>())
-            .map(|vec| {
-                vec.as_any()
-                    .downcast_ref::<Vec<T>>()
-                    .unwrap()
-                    .iter()
-                    .map(|item| format!("{:?}", item))
-                    .collect()
-            })
+            .get(&type_id)
+            .map(|vec| vec.debug_values())
             .unwrap_or_default()
     }
 
@@ -243,6 +326,64 @@ impl TypeBucket {
     //         .and_then(|boxed| boxed.downcast_mut())
     // }
 
+    /// Remove the first value equal to `value` from this `TypeBucket`.
+    ///
+    /// Returns whether a value was removed.
+    pub fn remove_first<T: 'static + PartialEq>(&mut self, value: &T) -> bool {
+        let type_id = TypeId::of::<T>();
+        let values = match self.map.get_mut(&type_id) {
+            Some(bucket) => bucket.as_any_mut().downcast_mut::<Vec<T>>().unwrap(),
+            None => return false,
+        };
+
+        match values.iter().position(|candidate| candidate == value) {
+            Some(position) => {
+                values.remove(position);
+                if values.is_empty() {
+                    self.map.remove(&type_id);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove all the values of type `T` from this `TypeBucket`.
+    pub fn take<T: 'static>(&mut self) -> Vec<T> {
+        match self.map.remove(&TypeId::of::<T>()) {
+            Some(mut bucket) => {
+                std::mem::take(bucket.as_any_mut().downcast_mut::<Vec<T>>().unwrap())
+            }
+            None => Vec::new(),
+        }
+    }
+
+    /// Remove all the values of the type of `type_id` from this `TypeBucket`.
+    ///
+    /// Returns whether there were any.
+    pub fn remove_any(&mut self, type_id: TypeId) -> bool {
+        self.map.remove(&type_id).is_some()
+    }
+
+    /// Move all the values of the type `type_id` from this `TypeBucket` after
+    /// the values of that type in `into`.
+    ///
+    /// Returns the number of values moved.
+    pub fn move_any(&mut self, type_id: TypeId, into: &mut TypeBucket) -> usize {
+        let bucket = match self.map.remove(&type_id) {
+            Some(bucket) => bucket,
+            None => return 0,
+        };
+        let count = bucket.count();
+        match into.map.get_mut(&type_id) {
+            Some(into_bucket) => into_bucket.append_bucket(bucket),
+            None => {
+                into.map.insert(type_id, bucket);
+            }
+        }
+        count
+    }
+
     // /// Remove a value from this `TypeBucket`.
     // ///
     // /// If a value of this type exists, it will be returned.
@@ -253,6 +394,16 @@ impl TypeBucket {
     //         .and_then(|boxed| boxed.downcast().ok().map(|boxed| *boxed))
     // }
 
+    /// Name of the type of `type_id`, if this `TypeBucket` has values of it.
+    pub fn type_name(&self, type_id: TypeId) -> Option<&'static str> {
+        self.map.get(&type_id).map(|bucket| bucket.type_name())
+    }
+
+    /// Iterate over the types of the values inserted into this `TypeBucket`.
+    pub fn type_ids(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.map.keys().copied()
+    }
+
     /// Clear the `TypeBucket` of all inserted values.
     #[inline]
     pub fn clear(&mut self) {
@@ -280,7 +431,7 @@ impl TypeBucket {
 
 #[test]
 fn test_type_map() {
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, PartialEq, Clone)]
     struct MyType(i32);
 
     #[derive(Debug, PartialEq, Default)]
@@ -26,9 +26,38 @@ impl<Value> TypeIdToMany<Value> {
             entry.push(value_to_add);
         }
     }
-    pub fn get<Type: 'static>(&self) -> &[Value] {
+    pub fn remove<Type: 'static>(&mut self, value_to_remove: &Value)
+    where
+        Value: PartialEq,
+    {
+        let type_id = TypeId::of::<Type>();
+        if let Some(entry) = self.map.get_mut(&type_id) {
+            entry.retain(|value| value != value_to_remove);
+            if entry.is_empty() {
+                self.map.remove(&type_id);
+            }
+        }
+    }
+    pub fn remove_any(&mut self, type_id: TypeId, value_to_remove: &Value)
+    where
+        Value: PartialEq,
+    {
+        if let Some(entry) = self.map.get_mut(&type_id) {
+            entry.retain(|value| value != value_to_remove);
+            if entry.is_empty() {
+                self.map.remove(&type_id);
+            }
+        }
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (TypeId, &[Value])> {
         self.map
-            .get(&TypeId::of::<Type>())
-            .map_or(&[], Vec::as_slice)
+            .iter()
+            .map(|(type_id, values)| (*type_id, values.as_slice()))
+    }
+    pub fn get_any(&self, type_id: TypeId) -> &[Value] {
+        self.map.get(&type_id).map_or(&[], Vec::as_slice)
+    }
+    pub fn get<Type: 'static>(&self) -> &[Value] {
+        self.get_any(TypeId::of::<Type>())
     }
 }